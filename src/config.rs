@@ -1,4 +1,6 @@
+use serde::Deserialize;
 use std::env;
+use std::fmt;
 
 #[derive(Clone, Debug)]
 /// Centralized application configuration loaded from environment variables.
@@ -7,32 +9,381 @@ pub struct Config {
     pub engine_path: String,
     /// Address and port to bind the Actix web server (e.g., "127.0.0.1:8080")
     pub server_addr: String,
-    /// GitHub personal access token for API operations
-    pub github_token: String,
-    /// GitHub repository in the format "owner" (e.g., "grim-kalman")
+    /// Where the README is published: "github", "gitlab", "gitea", "local-git" or "file"
+    pub publisher: String,
+    /// Page visitors are redirected to after an update; the configured host's profile or project
+    pub profile_url: String,
+    /// GitLab instance, project path ("group/project") and access token for the "gitlab" publisher
+    pub gitlab_url: String,
+    pub gitlab_project: Option<String>,
+    pub gitlab_token: Option<String>,
+    /// Gitea instance, repository ("owner/repo") and access token for the "gitea" publisher
+    pub gitea_url: Option<String>,
+    pub gitea_repo: Option<String>,
+    pub gitea_token: Option<String>,
+    /// Working tree the "local-git" publisher commits the README in
+    pub local_git_repo: Option<String>,
+    /// Remote (name or URL) the "local-git" publisher pushes to; commits stay local if unset
+    pub local_git_remote: Option<String>,
+    /// File the "file" publisher writes the README to
+    pub preview_file: String,
+    /// Serve the previewed README as HTML at /preview
+    pub preview_html: bool,
+    /// GitHub personal access token for API operations; not needed when a GitHub App is configured
+    pub github_token: Option<String>,
+    /// GitHub App credentials; used instead of the personal access token when all are set
+    pub github_app_id: Option<String>,
+    pub github_app_private_key_path: Option<String>,
+    pub github_app_installation_id: Option<u64>,
+    /// GitHub API root, e.g. "https://github.example.com/api/v3" for GitHub Enterprise Server
+    pub github_api_url: String,
+    /// GitHub web root that profiles and OAuth pages live on
+    pub github_web_url: String,
+    /// GitHub repository as "owner/repo", or "owner" for the profile repository "owner/owner"
+    /// (e.g., "grim-kalman")
     pub github_owner_repo: String,
     /// Branch to update (e.g., "main")
-    pub github_branch: String,
+    pub readme_branch: String,
     /// Path to the README file in the repository
-    pub github_readme_path: String,
+    pub readme_path: String,
+    /// READMEs the "github" publisher keeps in sync; defaults to the profile README above
+    pub github_targets: Vec<GithubTargetConfig>,
     /// Base URL for endpoint links (e.g., "https://your.domain.com")
     pub base_url: String,
+    /// Show the engine's evaluation bar and expected line under the board
+    pub show_evaluation: bool,
+    /// Search depth used when suggesting a move to the player
+    pub hint_depth: u32,
+    /// Optional Polyglot (.bin) opening book the engine replies from while in book
+    pub opening_book_path: Option<String>,
+    /// Optional Syzygy tablebase directory, passed to the engine and used for adjudication
+    pub syzygy_path: Option<String>,
+    /// Puzzle file (`id,fen,moves` per line) enabling the daily puzzle mode
+    pub puzzle_path: Option<String>,
+    /// Wall-clock hours each side has per move; no clock if unset
+    pub move_time_limit_hours: Option<u64>,
+    /// Let the engine move for a side that runs out of time instead of declaring a loss
+    pub timeout_engine_move: bool,
+    /// Directory storing finished and abandoned games (PGN and metadata)
+    pub archive_dir: String,
+    /// Number of recent results listed in the README; hidden if 0
+    pub recent_games: usize,
+    /// GitHub OAuth app credentials; visitor login is enabled when both are set
+    pub oauth_client_id: Option<String>,
+    pub oauth_client_secret: Option<String>,
+    /// OAuth endpoints, overridable to point at a fake provider
+    pub oauth_authorize_url: String,
+    pub oauth_token_url: String,
+    pub oauth_user_url: String,
+    pub oauth_org_membership_url: String,
+    /// Require visitors to sign in before playing moves or starting games; implied by
+    /// `allowed_org`
+    pub require_login: bool,
+    /// Only let members of this GitHub organization sign in
+    pub allowed_org: Option<String>,
+    /// Secret (at least 32 bytes) signing session cookies; random per start if unset
+    pub session_key: Option<String>,
+    /// Number of visitors shown on the leaderboard; hidden if 0
+    pub leaderboard_size: usize,
+    /// How README commits are made on GitHub: "git-data" (tree + commit + ref) or "contents"
+    pub github_publish_method: String,
+    /// Commit message for moves (`{player}`, `{san}`, `{engine_san}`, `{fen}`; `[...]` is
+    /// optional); the publishers' default is used if unset
+    pub commit_message_template: Option<String>,
+    /// Author and committer of README commits; the token's identity is used if unset
+    pub commit_author_name: Option<String>,
+    pub commit_author_email: Option<String>,
+    pub commit_committer_name: Option<String>,
+    pub commit_committer_email: Option<String>,
+    /// Append the FEN and/or PGN to commit messages so the git log doubles as a game record
+    pub commit_include_fen: bool,
+    pub commit_include_pgn: bool,
+    /// Token required for admin-only actions such as `/new?fen=...`; disabled if unset
+    pub admin_token: Option<String>,
 }
 
+/// A README listed in `GITHUB_TARGETS`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct GithubTargetConfig {
+    /// Repository in the format "owner/repo"
+    pub repo: String,
+    #[serde(rename = "path", default = "default_readme_path")]
+    pub readme_path: String,
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    /// Commit message template for this target, instead of the shared one
+    #[serde(default)]
+    pub template: Option<String>,
+    /// File holding the README's own content, with a `{board}` placeholder for the board;
+    /// the README is only the board if unset
+    #[serde(default)]
+    pub readme_template: Option<String>,
+}
+
+fn default_readme_path() -> String {
+    "README.md".to_string()
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+/// Reason the configuration could not be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A required variable is not set
+    Missing(&'static str),
+    /// A variable is set to a value that cannot be used
+    Invalid { name: &'static str, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Missing(name) => write!(f, "{} is not set", name),
+            ConfigError::Invalid { name, reason } => write!(f, "{} is invalid: {}", name, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
 impl Config {
-    /// Load configuration from environment variables. Panics if required variables are missing.
-    pub fn from_env() -> Result<Self, env::VarError> {
+    /// Load configuration from environment variables, failing if required variables are
+    /// missing or invalid.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let github_token = env::var("GITHUB_TOKEN").ok();
+        let github_app_id = env::var("GITHUB_APP_ID").ok();
+        let github_app_private_key_path = env::var("GITHUB_APP_PRIVATE_KEY_PATH").ok();
+        let github_app_installation_id = match env::var("GITHUB_APP_INSTALLATION_ID") {
+            Ok(id) => Some(id.parse().map_err(|_| ConfigError::Invalid {
+                name: "GITHUB_APP_INSTALLATION_ID",
+                reason: format!("expected a number, got '{}'", id),
+            })?),
+            Err(_) => None,
+        };
+        // GitHub App credentials are all-or-nothing, so a partial setup is reported
+        let app_vars = [
+            ("GITHUB_APP_ID", github_app_id.is_some()),
+            ("GITHUB_APP_PRIVATE_KEY_PATH", github_app_private_key_path.is_some()),
+            ("GITHUB_APP_INSTALLATION_ID", github_app_installation_id.is_some()),
+        ];
+        let has_app = app_vars.iter().all(|(_, set)| *set);
+        if !has_app && app_vars.iter().any(|(_, set)| *set) {
+            let (missing, _) = app_vars.iter().find(|(_, set)| !set).unwrap();
+            return Err(ConfigError::Missing(missing));
+        }
+        let publisher = env::var("PUBLISHER").unwrap_or_else(|_| "github".to_string());
+        let gitlab_project = env::var("GITLAB_PROJECT").ok();
+        let gitlab_token = env::var("GITLAB_TOKEN").ok();
+        let gitea_url = env::var("GITEA_URL")
+            .ok()
+            .map(|url| url.trim_end_matches('/').to_string());
+        let gitea_repo = env::var("GITEA_REPO").ok();
+        let gitea_token = env::var("GITEA_TOKEN").ok();
+        let local_git_repo = env::var("LOCAL_GIT_REPO").ok();
+        // Settings the chosen publisher cannot work without
+        let required: &[(&'static str, bool)] = match publisher.as_str() {
+            "github" => &[("GITHUB_TOKEN", github_token.is_some() || has_app)],
+            "gitlab" => &[
+                ("GITLAB_PROJECT", gitlab_project.is_some()),
+                ("GITLAB_TOKEN", gitlab_token.is_some()),
+            ],
+            "gitea" => &[
+                ("GITEA_URL", gitea_url.is_some()),
+                ("GITEA_REPO", gitea_repo.is_some()),
+                ("GITEA_TOKEN", gitea_token.is_some()),
+            ],
+            "local-git" => &[("LOCAL_GIT_REPO", local_git_repo.is_some())],
+            "file" => &[],
+            other => {
+                return Err(ConfigError::Invalid {
+                    name: "PUBLISHER",
+                    reason: format!(
+                        "expected github, gitlab, gitea, local-git or file, got '{}'",
+                        other
+                    ),
+                });
+            }
+        };
+        if let Some((missing, _)) = required.iter().find(|(_, set)| !set) {
+            return Err(ConfigError::Missing(missing));
+        }
+        let github_publish_method =
+            env::var("GITHUB_PUBLISH_METHOD").unwrap_or_else(|_| "git-data".to_string());
+        if !matches!(github_publish_method.as_str(), "git-data" | "contents") {
+            return Err(ConfigError::Invalid {
+                name: "GITHUB_PUBLISH_METHOD",
+                reason: format!("expected git-data or contents, got '{}'", github_publish_method),
+            });
+        }
+        let github_owner_repo =
+            env::var("GITHUB_OWNER_REPO").unwrap_or_else(|_| "grim-kalman".to_string());
+        // The GitHub names predate the other publishers and are still accepted
+        let readme_branch = env::var("README_BRANCH")
+            .or_else(|_| env::var("GITHUB_BRANCH"))
+            .unwrap_or_else(|_| "main".to_string());
+        let readme_path = env::var("README_PATH")
+            .or_else(|_| env::var("GITHUB_README_PATH"))
+            .unwrap_or_else(|_| "README.md".to_string());
+        let github_targets: Vec<GithubTargetConfig> = match env::var("GITHUB_TARGETS") {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| ConfigError::Invalid {
+                name: "GITHUB_TARGETS",
+                reason: e.to_string(),
+            })?,
+            // A bare owner refers to their profile repository "owner/owner"
+            Err(_) => vec![GithubTargetConfig {
+                repo: if github_owner_repo.contains('/') {
+                    github_owner_repo.clone()
+                } else {
+                    format!("{0}/{0}", github_owner_repo)
+                },
+                readme_path: readme_path.clone(),
+                branch: readme_branch.clone(),
+                template: None,
+                readme_template: None,
+            }],
+        };
+        let Some(first_target) = github_targets.first() else {
+            return Err(ConfigError::Invalid {
+                name: "GITHUB_TARGETS",
+                reason: "no targets listed".to_string(),
+            });
+        };
+        let allowed_org = env::var("GITHUB_ALLOWED_ORG").ok().filter(|o| !o.is_empty());
+        let require_login = env_flag("REQUIRE_LOGIN", false) || allowed_org.is_some();
+        let oauth_client_id = env::var("GITHUB_CLIENT_ID").ok();
+        let oauth_client_secret = env::var("GITHUB_CLIENT_SECRET").ok();
+        // Signing in needs an OAuth app
+        if require_login {
+            if oauth_client_id.is_none() {
+                return Err(ConfigError::Missing("GITHUB_CLIENT_ID"));
+            }
+            if oauth_client_secret.is_none() {
+                return Err(ConfigError::Missing("GITHUB_CLIENT_SECRET"));
+            }
+        }
+        let session_key = env::var("SESSION_KEY").ok();
+        if let Some(key) = &session_key
+            && key.len() < 32
+        {
+            return Err(ConfigError::Invalid {
+                name: "SESSION_KEY",
+                reason: format!("must be at least 32 bytes, got {}", key.len()),
+            });
+        }
+        let github_api_url = env::var("GITHUB_API_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| "https://api.github.com".to_string());
+        let github_web_url = env::var("GITHUB_WEB_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| "https://github.com".to_string());
+        let gitlab_url = env::var("GITLAB_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| "https://gitlab.com".to_string());
+        let base_url = env::var("BASE_URL")
+            .unwrap_or_else(|_| "https://rust-readme-chess.duckdns.org".to_string());
+        let preview_html = env_flag("PREVIEW_HTML", false);
+        let profile_url = env::var("PROFILE_URL").unwrap_or_else(|_| match publisher.as_str() {
+            "file" if preview_html => format!("{}/preview", base_url),
+            "gitlab" => format!("{}/{}", gitlab_url, gitlab_project.as_deref().unwrap_or("")),
+            "gitea" => format!(
+                "{}/{}",
+                gitea_url.as_deref().unwrap_or(""),
+                gitea_repo.as_deref().unwrap_or("")
+            ),
+            _ => format!("{}/{}", github_web_url, profile_path(&first_target.repo)),
+        });
         Ok(Self {
             engine_path: env::var("ENGINE_PATH").unwrap_or_else(|_| "engine/stockfish".to_string()),
             server_addr: env::var("SERVER_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string()),
-            github_token: env::var("GITHUB_TOKEN")?,
-            github_owner_repo: env::var("GITHUB_OWNER_REPO")
-                .unwrap_or_else(|_| "grim-kalman".to_string()),
-            github_branch: env::var("GITHUB_BRANCH").unwrap_or_else(|_| "main".to_string()),
-            github_readme_path: env::var("GITHUB_README_PATH")
-                .unwrap_or_else(|_| "README.md".to_string()),
-            base_url: env::var("BASE_URL")
-                .unwrap_or_else(|_| "https://rust-readme-chess.duckdns.org".to_string()),
+            publisher,
+            profile_url,
+            gitlab_url,
+            gitlab_project,
+            gitlab_token,
+            gitea_url,
+            gitea_repo,
+            gitea_token,
+            local_git_repo,
+            local_git_remote: env::var("LOCAL_GIT_REMOTE").ok().filter(|r| !r.is_empty()),
+            preview_file: env::var("PREVIEW_FILE")
+                .unwrap_or_else(|_| "preview/README.md".to_string()),
+            preview_html,
+            github_token,
+            github_app_id,
+            github_app_private_key_path,
+            github_app_installation_id,
+            github_api_url: github_api_url.clone(),
+            github_web_url: github_web_url.clone(),
+            github_owner_repo,
+            readme_branch,
+            readme_path,
+            github_targets,
+            base_url,
+            show_evaluation: env_flag("SHOW_EVALUATION", true),
+            hint_depth: env::var("HINT_DEPTH")
+                .ok()
+                .and_then(|d| d.parse().ok())
+                .unwrap_or(10),
+            opening_book_path: env::var("OPENING_BOOK_PATH").ok(),
+            syzygy_path: env::var("SYZYGY_PATH").ok(),
+            puzzle_path: env::var("PUZZLE_PATH").ok(),
+            move_time_limit_hours: env::var("MOVE_TIME_LIMIT_HOURS")
+                .ok()
+                .and_then(|h| h.parse().ok())
+                .filter(|&h| h > 0),
+            timeout_engine_move: env_flag("TIMEOUT_ENGINE_MOVE", false),
+            archive_dir: env::var("ARCHIVE_DIR").unwrap_or_else(|_| "archive".to_string()),
+            recent_games: env::var("RECENT_GAMES")
+                .ok()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(5),
+            oauth_client_id,
+            oauth_client_secret,
+            oauth_authorize_url: env::var("OAUTH_AUTHORIZE_URL")
+                .unwrap_or_else(|_| format!("{}/login/oauth/authorize", github_web_url)),
+            oauth_token_url: env::var("OAUTH_TOKEN_URL")
+                .unwrap_or_else(|_| format!("{}/login/oauth/access_token", github_web_url)),
+            oauth_user_url: env::var("OAUTH_USER_URL")
+                .unwrap_or_else(|_| format!("{}/user", github_api_url)),
+            oauth_org_membership_url: env::var("OAUTH_ORG_MEMBERSHIP_URL").unwrap_or_else(|_| {
+                format!("{}/user/memberships/orgs/{{org}}", github_api_url)
+            }),
+            require_login,
+            allowed_org,
+            session_key,
+            leaderboard_size: env::var("LEADERBOARD_SIZE")
+                .ok()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(10),
+            github_publish_method,
+            commit_message_template: env::var("COMMIT_MESSAGE_TEMPLATE").ok(),
+            commit_author_name: env::var("COMMIT_AUTHOR_NAME").ok(),
+            commit_author_email: env::var("COMMIT_AUTHOR_EMAIL").ok(),
+            commit_committer_name: env::var("COMMIT_COMMITTER_NAME").ok(),
+            commit_committer_email: env::var("COMMIT_COMMITTER_EMAIL").ok(),
+            commit_include_fen: env_flag("COMMIT_INCLUDE_FEN", false),
+            commit_include_pgn: env_flag("COMMIT_INCLUDE_PGN", false),
+            admin_token: env::var("ADMIN_TOKEN").ok().filter(|t| !t.is_empty()),
         })
     }
 }
+
+/// Web page for a GitHub repository: the owner's profile for profile READMEs ("owner/owner" or
+/// an organization's "owner/.github"), the repository itself otherwise.
+fn profile_path(repo: &str) -> &str {
+    match repo.split_once('/') {
+        Some((owner, name)) if name == owner || name == ".github" => owner,
+        _ => repo,
+    }
+}
+
+/// Read a boolean flag ("true"/"1" or "false"/"0"), falling back to the default if unset or unrecognized.
+fn env_flag(name: &str, default: bool) -> bool {
+    match env::var(name).as_deref() {
+        Ok("true") | Ok("1") => true,
+        Ok("false") | Ok("0") => false,
+        _ => default,
+    }
+}
@@ -1,37 +1,114 @@
 use crate::config::Config;
-use crate::services::chess_service::ChessService;
-use crate::services::github_service::GithubService;
+use crate::services::archive_service::{
+    ArchiveService, ArchivedGame, LeaderboardEntry, MoveRecord,
+};
+use crate::services::chess_service::{ChessService, Opponent};
+use crate::services::file_publisher;
+use crate::services::publisher::{CommitContext, ReadmePublisher};
+use crate::services::oauth_service::OAuthService;
 use crate::utils::printer::MarkdownPrinter;
-use actix_web::{HttpResponse, Responder, web};
+use crate::controllers::session;
+use actix_web::cookie::Key;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use rand::Rng;
+use rand::distributions::Alphanumeric;
 use serde::Deserialize;
 use std::sync::Arc;
-use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
 
-// Redirects the user to the GitHub profile with a nanosecond cachebuster to force refresh.
-fn redirect_to_github(config: &Config) -> actix_web::HttpResponse {
+/// Number of attributed moves listed under "Recent moves by".
+const RECENT_MOVERS: usize = 5;
+
+// Redirects the user to the profile or project page showing the README, with a nanosecond
+// cachebuster to force refresh.
+fn redirect_to_profile(config: &Config) -> actix_web::HttpResponse {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_nanos())
         .unwrap_or(0);
-    let redirect_url = format!(
-        "https://github.com/{}?cb={}",
-        config.github_owner_repo, nanos
-    );
+    let redirect_url = format!("{}?cb={}", config.profile_url, nanos);
     actix_web::HttpResponse::SeeOther()
         .append_header(("Location", redirect_url))
         .finish()
 }
 
-// Updates the README on GitHub, polls until updated, and redirects the user.
+// Lists the most recent archived games for the README, if enabled.
+fn recent_games(service: &ChessService, config: &Config) -> Vec<ArchivedGame> {
+    let Some(archive) = service.get_archive() else {
+        return Vec::new();
+    };
+    archive.recent(config.recent_games).unwrap_or_else(|e| {
+        log::error!("Failed to read game archive: {}", e);
+        Vec::new()
+    })
+}
+
+// Lists the latest moves by signed-in visitors for the README.
+fn recent_moves(service: &ChessService) -> Vec<MoveRecord> {
+    let Some(archive) = service.get_archive() else {
+        return Vec::new();
+    };
+    archive.recent_moves(RECENT_MOVERS).unwrap_or_else(|e| {
+        log::error!("Failed to read move log: {}", e);
+        Vec::new()
+    })
+}
+
+// Builds the all-time leaderboard for the README, if enabled.
+fn leaderboard(service: &ChessService, config: &Config) -> Vec<LeaderboardEntry> {
+    let Some(archive) = service.get_archive() else {
+        return Vec::new();
+    };
+    archive.leaderboard(config.leaderboard_size).unwrap_or_else(|e| {
+        log::error!("Failed to build leaderboard: {}", e);
+        Vec::new()
+    })
+}
+
+// Checks a request's token against the configured admin token.
+fn is_admin(token: Option<&str>, config: &Config) -> bool {
+    matches!((token, &config.admin_token), (Some(given), Some(expected)) if given == expected)
+}
+
+// Describes an update that is not a move, for the README commit.
+async fn commit_context(service: &mut ChessService, summary: &str) -> CommitContext {
+    CommitContext {
+        summary: summary.to_string(),
+        fen: service.get_fen().await.unwrap_or_default(),
+        pgn: service.get_pgn(),
+        ..Default::default()
+    }
+}
+
+// Describes the visitor's move and the reply, i.e. the moves played after `from_ply`.
+async fn move_commit_context(
+    service: &mut ChessService,
+    player: Option<String>,
+    from_ply: usize,
+) -> CommitContext {
+    let mut new_moves = service.get_san_moves().into_iter().skip(from_ply);
+    let (san, engine_san) = (new_moves.next(), new_moves.next());
+    CommitContext {
+        player,
+        san,
+        engine_san,
+        ..commit_context(service, "Play a move").await
+    }
+}
+
+// Publishes the README, waits until the update shows, and redirects the user.
 async fn update_and_redirect(
     board_md: String,
-    github_service: &Arc<GithubService>,
+    context: CommitContext,
+    publisher: &Arc<dyn ReadmePublisher>,
     config: &Config,
 ) -> actix_web::HttpResponse {
-    let _ = github_service.update_readme(&board_md).await;
-    let _ = github_service.poll_readme_until_updated(&board_md, 10).await;
-    redirect_to_github(config)
+    if let Err(e) = publisher.publish(&board_md, &context).await {
+        log::error!("Failed to publish README: {}", e);
+    }
+    let _ = publisher.wait_until_published(&board_md).await;
+    redirect_to_profile(config)
 }
 
 // Helper to get the current board state (FEN, valid moves, selected square)
@@ -48,6 +125,44 @@ async fn get_board_state(
     Ok((fen, valid_moves, selected))
 }
 
+// Helper to render the current game (board, result, notices) as README markdown
+async fn render_game(
+    service: &mut ChessService,
+    config: &Config,
+) -> Result<String, actix_web::HttpResponse> {
+    let (fen, valid_moves, selected) = get_board_state(service).await?;
+    let checkers = service.get_checkers().await.map_err(|e| {
+        actix_web::HttpResponse::InternalServerError().body(format!("Checkers error: {}", e))
+    })?;
+    let analysis = if config.show_evaluation {
+        service.get_analysis().cloned()
+    } else {
+        None
+    };
+    let printer = MarkdownPrinter::new(config.base_url.clone(), config.github_owner_repo.clone())
+        .with_result(service.get_result().cloned())
+        .with_notice(service.get_notice().map(str::to_string))
+        .with_checkers(checkers)
+        .with_captured(service.get_captured())
+        .with_analysis(analysis)
+        .with_hint(service.get_hint().map(str::to_string), service.get_hints_used())
+        .with_book(service.is_in_book())
+        .with_opening(service.get_opening().cloned())
+        .with_tablebase(service.get_tablebase_probe().copied())
+        .with_chess960(service.get_chess960_id())
+        .with_start_name(service.get_start_name().map(str::to_string))
+        .with_puzzle(service.get_puzzle())
+        .with_human_vs_human(service.get_opponent() == Opponent::Human)
+        .with_clock(service.get_clock(SystemTime::now()))
+        .with_recent_games(recent_games(service, config))
+        .with_recent_moves(recent_moves(service))
+        .with_leaderboard(leaderboard(service, config))
+        .with_login(config.oauth_client_id.is_some() && config.oauth_client_secret.is_some())
+        .with_web_url(config.github_web_url.clone())
+        .with_profile_url(config.profile_url.clone());
+    Ok(printer.print(fen, valid_moves, &selected))
+}
+
 #[derive(Deserialize)]
 /// Query for /play endpoint. `move` is reserved, so we use `mv`.
 pub struct PlayQuery {
@@ -56,22 +171,28 @@ pub struct PlayQuery {
 
 // Handles a play (move) request.
 pub async fn play(
+    req: HttpRequest,
     query: web::Query<PlayQuery>,
     chess_service: web::Data<Arc<Mutex<ChessService>>>,
-    github_service: web::Data<Arc<GithubService>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
     config: web::Data<Config>,
+    key: web::Data<Key>,
 ) -> impl Responder {
-    let mut service = chess_service.lock().unwrap();
-    if let Err(e) = service.play(&query.mv).await {
+    let player = match session::signed_in_user(&req, &config, &key) {
+        Ok(player) => player,
+        Err(resp) => return resp,
+    };
+    let mut service = chess_service.lock().await;
+    let ply = service.get_san_moves().len();
+    if let Err(e) = service.play_by(&query.mv, player.as_deref()).await {
         return HttpResponse::BadRequest().body(format!("Invalid move: {}", e));
     }
-    let (fen, valid_moves, _) = match get_board_state(&mut service).await {
-        Ok(data) => data,
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
         Err(resp) => return resp,
     };
-    let printer = MarkdownPrinter::new(config.base_url.clone(), config.github_owner_repo.clone());
-    let board_md = printer.print(fen, valid_moves, "");
-    update_and_redirect(board_md, &github_service, &config).await
+    let context = move_commit_context(&mut service, player, ply).await;
+    update_and_redirect(board_md, context, &publisher, &config).await
 }
 
 #[derive(Deserialize)]
@@ -82,39 +203,328 @@ pub struct SelectQuery {
 
 // Handles a select (piece selection) request.
 pub async fn select(
+    req: HttpRequest,
     query: web::Query<SelectQuery>,
     chess_service: web::Data<Arc<Mutex<ChessService>>>,
-    github_service: web::Data<Arc<GithubService>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
     config: web::Data<Config>,
+    key: web::Data<Key>,
 ) -> impl Responder {
-    let mut service = chess_service.lock().unwrap();
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
+    }
+    let mut service = chess_service.lock().await;
     if let Err(e) = service.select(&query.square).await {
         return HttpResponse::BadRequest().body(format!("Select error: {}", e));
     }
-    let (fen, valid_moves, selected) = match get_board_state(&mut service).await {
-        Ok(data) => data,
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
         Err(resp) => return resp,
     };
-    let printer = MarkdownPrinter::new(config.base_url.clone(), config.github_owner_repo.clone());
-    let board_md = printer.print(fen, valid_moves, &selected);
-    update_and_redirect(board_md, &github_service, &config).await
+    let context = commit_context(&mut service, &format!("Select {}", query.square)).await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+#[derive(Deserialize)]
+/// Query for /new endpoint, e.g. `?variant=chess960&id=518`, `?position=lucena`
+/// or `?fen=...&token=...` (admin only).
+pub struct NewGameQuery {
+    pub variant: Option<String>,
+    /// Chess960 position number (0-959); random if omitted
+    pub id: Option<u32>,
+    /// Key of a named position from the catalog
+    pub position: Option<String>,
+    /// Custom starting position; requires the admin token
+    pub fen: Option<String>,
+    pub token: Option<String>,
+    /// "engine" or "human"; keeps the current opponent if omitted
+    pub opponent: Option<String>,
 }
 
 // Handles a new game request.
 pub async fn new_game(
+    req: HttpRequest,
+    query: web::Query<NewGameQuery>,
+    chess_service: web::Data<Arc<Mutex<ChessService>>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
+    config: web::Data<Config>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
+    }
+    if query.fen.is_some() && !is_admin(query.token.as_deref(), &config) {
+        return HttpResponse::Forbidden().body("Starting from a FEN requires the admin token");
+    }
+    let opponent = match query.opponent.as_deref() {
+        None => None,
+        Some("engine") => Some(Opponent::Engine),
+        Some("human") => Some(Opponent::Human),
+        Some(other) => {
+            return HttpResponse::BadRequest().body(format!("Unknown opponent: {}", other));
+        }
+    };
+    let mut service = chess_service.lock().await;
+    if let Some(opponent) = opponent {
+        service.set_opponent(opponent);
+    }
+    let started = match (query.variant.as_deref(), &query.fen, &query.position) {
+        (Some("chess960"), _, _) => service.new_chess960_game(query.id).await.map(|_| ()),
+        (None | Some("standard"), Some(fen), _) => service.new_game_from_fen(fen).await,
+        (None | Some("standard"), None, Some(key)) => service.new_game_from_named(key).await,
+        (None | Some("standard"), None, None) => service.new_game().await,
+        (Some(other), _, _) => {
+            return HttpResponse::BadRequest().body(format!("Unknown variant: {}", other));
+        }
+    };
+    if let Err(e) = started {
+        return HttpResponse::BadRequest().body(format!("New game error: {}", e));
+    }
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
+        Err(resp) => return resp,
+    };
+    let context = commit_context(&mut service, "Start a new game").await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+// Handles a resign request.
+pub async fn resign(
+    req: HttpRequest,
+    chess_service: web::Data<Arc<Mutex<ChessService>>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
+    config: web::Data<Config>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
+    }
+    let mut service = chess_service.lock().await;
+    if let Err(e) = service.resign().await {
+        return HttpResponse::BadRequest().body(format!("Resign error: {}", e));
+    }
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
+        Err(resp) => return resp,
+    };
+    let context = commit_context(&mut service, "Resign").await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+// Handles a draw claim or offer.
+pub async fn draw(
+    req: HttpRequest,
     chess_service: web::Data<Arc<Mutex<ChessService>>>,
-    github_service: web::Data<Arc<GithubService>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
     config: web::Data<Config>,
+    key: web::Data<Key>,
 ) -> impl Responder {
-    let mut service = chess_service.lock().unwrap();
-    if let Err(e) = service.new_game().await {
-        return HttpResponse::InternalServerError().body(format!("New game error: {}", e));
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
     }
-    let (fen, valid_moves, _) = match get_board_state(&mut service).await {
-        Ok(data) => data,
+    let mut service = chess_service.lock().await;
+    let drawn = match service.draw().await {
+        Ok(drawn) => drawn,
+        Err(e) => return HttpResponse::BadRequest().body(format!("Draw error: {}", e)),
+    };
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
         Err(resp) => return resp,
     };
-    let printer = MarkdownPrinter::new(config.base_url.clone(), config.github_owner_repo.clone());
-    let board_md = printer.print(fen, valid_moves, "");
-    update_and_redirect(board_md, &github_service, &config).await
+    let summary = if drawn { "Agree to a draw" } else { "Offer a draw" };
+    let context = commit_context(&mut service, summary).await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+// Handles a hint request: highlights the engine's suggested move without playing it.
+pub async fn hint(
+    req: HttpRequest,
+    chess_service: web::Data<Arc<Mutex<ChessService>>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
+    config: web::Data<Config>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
+    }
+    let mut service = chess_service.lock().await;
+    if let Err(e) = service.hint(config.hint_depth).await {
+        return HttpResponse::BadRequest().body(format!("Hint error: {}", e));
+    }
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
+        Err(resp) => return resp,
+    };
+    let context = commit_context(&mut service, "Show a hint").await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+// Starts the daily puzzle: the player solves a tactic instead of playing the engine.
+pub async fn puzzle(
+    req: HttpRequest,
+    chess_service: web::Data<Arc<Mutex<ChessService>>>,
+    publisher: web::Data<Arc<dyn ReadmePublisher>>,
+    config: web::Data<Config>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    if let Err(resp) = session::signed_in_user(&req, &config, &key) {
+        return resp;
+    }
+    let mut service = chess_service.lock().await;
+    if let Err(e) = service.new_daily_puzzle().await {
+        return HttpResponse::BadRequest().body(format!("Puzzle error: {}", e));
+    }
+    let board_md = match render_game(&mut service, &config).await {
+        Ok(md) => md,
+        Err(resp) => return resp,
+    };
+    let context = commit_context(&mut service, "Start the daily puzzle").await;
+    update_and_redirect(board_md, context, &publisher, &config).await
+}
+
+/// Renders the current game and publishes it to the README, for updates that
+/// do not come from a visitor's request (e.g., the move clock running out).
+pub async fn publish_board(
+    service: &mut ChessService,
+    publisher: &dyn ReadmePublisher,
+    config: &Config,
+    summary: &str,
+) -> anyhow::Result<()> {
+    let board_md = render_game(service, config)
+        .await
+        .map_err(|_| anyhow::anyhow!("Failed to render the board"))?;
+    let context = commit_context(service, summary).await;
+    publisher.publish(&board_md, &context).await
+}
+
+// Lists archived games as JSON, most recent first.
+pub async fn archive(archive: web::Data<Arc<ArchiveService>>) -> impl Responder {
+    match archive.list() {
+        Ok(games) => HttpResponse::Ok().json(games),
+        Err(e) => HttpResponse::InternalServerError().body(format!("Archive error: {}", e)),
+    }
+}
+
+// Downloads the PGN of an archived game.
+pub async fn archive_pgn(
+    path: web::Path<u64>,
+    archive: web::Data<Arc<ArchiveService>>,
+) -> impl Responder {
+    let id = path.into_inner();
+    match archive.pgn(id) {
+        Ok(pgn) => HttpResponse::Ok()
+            .content_type("application/x-chess-pgn")
+            .body(pgn),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            HttpResponse::NotFound().body(format!("No archived game {}", id))
+        }
+        Err(e) => HttpResponse::InternalServerError().body(format!("Archive error: {}", e)),
+    }
+}
+
+// Shows the README last written by the file publisher as HTML, when previews are enabled.
+pub async fn preview(config: web::Data<Config>) -> impl Responder {
+    if config.publisher != "file" || !config.preview_html {
+        return HttpResponse::NotFound().body("Preview is disabled");
+    }
+    match std::fs::read_to_string(&config.preview_file) {
+        Ok(markdown) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(file_publisher::render_html(&markdown)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            HttpResponse::NotFound().body("Nothing has been published yet")
+        }
+        Err(e) => HttpResponse::InternalServerError().body(format!("Preview error: {}", e)),
+    }
+}
+
+#[derive(Deserialize)]
+/// Query GitHub sends back to /oauth/callback.
+pub struct OAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+// Starts the GitHub OAuth web flow, remembering an anti-forgery state in a signed cookie.
+pub async fn login(
+    oauth_service: Option<web::Data<Arc<OAuthService>>>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    let Some(oauth_service) = oauth_service else {
+        return HttpResponse::NotFound().body("Login is not configured");
+    };
+    let state: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    HttpResponse::SeeOther()
+        .cookie(session::signed_cookie(&key, session::STATE_COOKIE, &state))
+        .append_header(("Location", oauth_service.authorize_url(&state)))
+        .finish()
+}
+
+// Completes the OAuth flow: checks the state, looks up the visitor's GitHub login
+// and signs them in with a session cookie.
+pub async fn oauth_callback(
+    req: HttpRequest,
+    query: web::Query<OAuthCallbackQuery>,
+    oauth_service: Option<web::Data<Arc<OAuthService>>>,
+    config: web::Data<Config>,
+    key: web::Data<Key>,
+) -> impl Responder {
+    let Some(oauth_service) = oauth_service else {
+        return HttpResponse::NotFound().body("Login is not configured");
+    };
+    if let Some(error) = &query.error {
+        return HttpResponse::BadRequest().body(format!("Login failed: {}", error));
+    }
+    let expected_state = session::read_signed(&req, &key, session::STATE_COOKIE);
+    if query.state.is_none() || query.state != expected_state {
+        return HttpResponse::BadRequest().body("Login failed: state mismatch");
+    }
+    let Some(code) = &query.code else {
+        return HttpResponse::BadRequest().body("Login failed: missing code");
+    };
+    let token = match oauth_service.exchange_code(code).await {
+        Ok(token) => token,
+        Err(e) => return HttpResponse::BadGateway().body(format!("Login failed: {}", e)),
+    };
+    let login = match oauth_service.fetch_login(&token).await {
+        Ok(login) => login,
+        Err(e) => return HttpResponse::BadGateway().body(format!("Login failed: {}", e)),
+    };
+    if let Some(org) = &config.allowed_org {
+        match oauth_service.is_org_member(&token, org).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return HttpResponse::Forbidden()
+                    .body(format!("Only members of {} can play on this board", org));
+            }
+            Err(e) => return HttpResponse::BadGateway().body(format!("Login failed: {}", e)),
+        }
+    }
+    log::info!("Signed in {}", login);
+    // Resume the request that required login, if it was a local path
+    let return_to = session::read_signed(&req, &key, session::RETURN_COOKIE)
+        .filter(|path| path.starts_with('/') && !path.starts_with("//"));
+    let mut resp = match return_to {
+        Some(path) => HttpResponse::SeeOther()
+            .append_header(("Location", format!("{}{}", config.base_url, path)))
+            .finish(),
+        None => redirect_to_profile(&config),
+    };
+    let _ = resp.add_cookie(&session::session_cookie(&key, &login));
+    for name in [session::STATE_COOKIE, session::RETURN_COOKIE] {
+        let _ = resp.add_removal_cookie(&session::signed_cookie(&key, name, ""));
+    }
+    resp
+}
+
+// Signs the visitor out by removing the session cookie.
+pub async fn logout(config: web::Data<Config>, key: web::Data<Key>) -> impl Responder {
+    let mut resp = redirect_to_profile(&config);
+    let _ = resp.add_removal_cookie(&session::session_cookie(&key, ""));
+    resp
 }
@@ -1,6 +1,9 @@
 use actix_web::web;
 
 mod controller;
+pub mod session;
+
+pub use controller::publish_board;
 
 pub fn init_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/play").route(web::get().to(crate::controllers::controller::play)))
@@ -9,5 +12,37 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
         )
         .service(
             web::resource("/new").route(web::get().to(crate::controllers::controller::new_game)),
+        )
+        .service(
+            web::resource("/resign").route(web::get().to(crate::controllers::controller::resign)),
+        )
+        .service(
+            web::resource("/draw").route(web::get().to(crate::controllers::controller::draw)),
+        )
+        .service(
+            web::resource("/hint").route(web::get().to(crate::controllers::controller::hint)),
+        )
+        .service(
+            web::resource("/puzzle").route(web::get().to(crate::controllers::controller::puzzle)),
+        )
+        .service(
+            web::resource("/archive").route(web::get().to(crate::controllers::controller::archive)),
+        )
+        .service(
+            web::resource("/archive/{id}.pgn")
+                .route(web::get().to(crate::controllers::controller::archive_pgn)),
+        )
+        .service(
+            web::resource("/preview").route(web::get().to(crate::controllers::controller::preview)),
+        )
+        .service(
+            web::resource("/login").route(web::get().to(crate::controllers::controller::login)),
+        )
+        .service(
+            web::resource("/oauth/callback")
+                .route(web::get().to(crate::controllers::controller::oauth_callback)),
+        )
+        .service(
+            web::resource("/logout").route(web::get().to(crate::controllers::controller::logout)),
         );
 }
@@ -0,0 +1,63 @@
+use crate::config::Config;
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, CookieJar, Key, SameSite};
+
+/// Cookie holding the signed-in visitor's GitHub login.
+pub const SESSION_COOKIE: &str = "readme_chess_session";
+/// Cookie holding the anti-forgery state while the OAuth flow is in progress.
+pub const STATE_COOKIE: &str = "readme_chess_oauth_state";
+/// Cookie holding the request to resume once the visitor has signed in.
+pub const RETURN_COOKIE: &str = "readme_chess_return_to";
+/// How long a visitor stays signed in.
+const SESSION_DAYS: i64 = 30;
+
+/// GitHub login of the signed-in visitor, if the session cookie is present and authentic.
+pub fn current_user(req: &HttpRequest, key: &Key) -> Option<String> {
+    read_signed(req, key, SESSION_COOKIE)
+}
+
+/// The signed-in visitor, or, when login is required and nobody is signed in,
+/// a redirect to /login that resumes this request afterwards.
+pub fn signed_in_user(
+    req: &HttpRequest,
+    config: &Config,
+    key: &Key,
+) -> Result<Option<String>, HttpResponse> {
+    let user = current_user(req, key);
+    if user.is_some() || !config.require_login {
+        return Ok(user);
+    }
+    let return_to = req.uri().path_and_query().map_or("/", |pq| pq.as_str());
+    Err(HttpResponse::SeeOther()
+        .cookie(signed_cookie(key, RETURN_COOKIE, return_to))
+        .append_header(("Location", format!("{}/login", config.base_url)))
+        .finish())
+}
+
+/// Session cookie signing in the given GitHub login.
+pub fn session_cookie(key: &Key, login: &str) -> Cookie<'static> {
+    let mut cookie = signed_cookie(key, SESSION_COOKIE, login);
+    cookie.set_max_age(Duration::days(SESSION_DAYS));
+    cookie
+}
+
+/// Read a cookie, returning its value only if the signature is valid.
+pub fn read_signed(req: &HttpRequest, key: &Key, name: &str) -> Option<String> {
+    let mut jar = CookieJar::new();
+    jar.add_original(req.cookie(name)?);
+    jar.signed(key).get(name).map(|c| c.value().to_string())
+}
+
+/// Build an HTTP-only cookie whose value is signed with the key.
+pub fn signed_cookie(key: &Key, name: &str, value: &str) -> Cookie<'static> {
+    let mut jar = CookieJar::new();
+    jar.signed_mut(key).add(
+        Cookie::build(name.to_string(), value.to_string())
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .finish(),
+    );
+    jar.get(name).cloned().expect("cookie was just added")
+}
@@ -1,16 +1,33 @@
-use crate::services::chess_service::ChessService;
+use crate::services::archive_service::ArchiveService;
+use crate::services::chess_service::{ChessService, TimeoutAction};
 use crate::services::engine_service::EngineService;
-use crate::services::github_service::{GithubConfig, GithubService};
+use crate::services::file_publisher::{FileConfig, FilePublisher};
+use crate::services::github_auth::{GithubAppAuth, GithubAuth};
+use crate::services::github_service::{GithubConfig, GithubService, GithubTarget, PublishMethod};
+use crate::services::gitea_publisher::{GiteaConfig, GiteaPublisher};
+use crate::services::gitlab_publisher::{GitlabConfig, GitlabPublisher};
+use crate::services::local_git_publisher::{LocalGitConfig, LocalGitPublisher};
+use crate::services::publisher::{
+    CommitIdentity, CommitSettings, DEFAULT_COMMIT_TEMPLATE, ReadmePublisher,
+};
+use crate::services::oauth_service::{OAuthConfig, OAuthService};
+use crate::utils::polyglot::PolyglotBook;
+use crate::utils::puzzles::PuzzleSet;
+use crate::utils::tablebase::Tablebase;
+use actix_web::cookie::Key;
 use actix_web::{App, HttpServer, web};
-use env_logger;
 use std::sync::Arc;
-use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
 
 mod config;
 mod controllers;
 mod services;
 mod utils;
 
+/// How often the background task checks the move clock.
+const CLOCK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // Set up logging for debugging
@@ -24,29 +41,223 @@ async fn main() -> std::io::Result<()> {
     let server_addr = config.server_addr.clone();
 
     // Initialize core services
-    let engine = EngineService::start(&config.engine_path)
+    let engine = EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
         .await
         .expect("Failed to start engine");
-    let chess_service = Arc::new(Mutex::new(ChessService::new(engine)));
-    let github_config = Arc::new(GithubConfig {
-        token: config.github_token.clone(),
-        api_url: "https://api.github.com/repos".to_string(),
-        owner_repo: config.github_owner_repo.clone(),
-        readme_path: config.github_readme_path.clone(),
-        branch: config.github_branch.clone(),
-    });
-    let github_service = Arc::new(GithubService::new(github_config));
+    let archive =
+        Arc::new(ArchiveService::open(&config.archive_dir).expect("Failed to open game archive"));
+    let mut chess_service = ChessService::new(engine).with_archive(archive.clone());
+    if let Some(path) = &config.opening_book_path {
+        let book = PolyglotBook::open(path).expect("Failed to load opening book");
+        chess_service = chess_service.with_opening_book(book);
+    }
+    if let Some(path) = &config.syzygy_path {
+        let tablebase = Tablebase::open(path).expect("Failed to read tablebase directory");
+        chess_service = chess_service.with_tablebase(tablebase);
+    }
+    if let Some(path) = &config.puzzle_path {
+        let puzzles = PuzzleSet::open(path).expect("Failed to load puzzle file");
+        chess_service = chess_service.with_puzzles(puzzles);
+    }
+    if let Some(hours) = config.move_time_limit_hours {
+        let on_timeout = if config.timeout_engine_move {
+            TimeoutAction::EngineMove
+        } else {
+            TimeoutAction::Forfeit
+        };
+        chess_service =
+            chess_service.with_move_clock(Duration::from_secs(hours * 3_600), on_timeout);
+    }
+    let chess_service = Arc::new(Mutex::new(chess_service));
+    let publisher = build_publisher(&config);
+
+    // Visitor login through GitHub OAuth, if an OAuth app is configured
+    let oauth_service = match (&config.oauth_client_id, &config.oauth_client_secret) {
+        (Some(client_id), Some(client_secret)) => Some(Arc::new(OAuthService::new(Arc::new(
+            OAuthConfig {
+                client_id: client_id.clone(),
+                client_secret: client_secret.clone(),
+                authorize_url: config.oauth_authorize_url.clone(),
+                token_url: config.oauth_token_url.clone(),
+                user_url: config.oauth_user_url.clone(),
+                org_membership_url: config.oauth_org_membership_url.clone(),
+                scope: config.allowed_org.as_ref().map(|_| "read:org".to_string()),
+                redirect_url: format!("{}/oauth/callback", config.base_url),
+            },
+        )))),
+        _ => None,
+    };
+    let session_key = match &config.session_key {
+        Some(secret) => Key::derive_from(secret.as_bytes()),
+        None => {
+            log::warn!("SESSION_KEY not set; visitors will be signed out on restart");
+            Key::generate()
+        }
+    };
+
+    // Adjudicate games whose move clock has run out
+    if config.move_time_limit_hours.is_some() {
+        let (chess_service, publisher, config) =
+            (chess_service.clone(), publisher.clone(), config.clone());
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLOCK_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let mut service = chess_service.lock().await;
+                match service.check_clock(SystemTime::now()).await {
+                    Ok(true) => {
+                        let published = controllers::publish_board(
+                            &mut service,
+                            publisher.as_ref(),
+                            &config,
+                            "Move clock ran out",
+                        )
+                        .await;
+                        if let Err(e) = published {
+                            log::error!("Failed to publish board after timeout: {}", e);
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => log::error!("Clock check failed: {}", e),
+                }
+            }
+        });
+    }
 
     // Start Actix web server
     HttpServer::new(move || {
-        App::new()
+        let mut app = App::new()
             .app_data(web::Data::new(config.clone()))
             .app_data(web::Data::new(chess_service.clone()))
-            .app_data(web::Data::new(github_service.clone()))
-            .configure(controllers::init_routes)
+            .app_data(web::Data::new(publisher.clone()))
+            .app_data(web::Data::new(archive.clone()))
+            .app_data(web::Data::new(session_key.clone()));
+        if let Some(oauth_service) = &oauth_service {
+            app = app.app_data(web::Data::new(oauth_service.clone()));
+        }
+        app.configure(controllers::init_routes)
     })
     .workers(1)
     .bind(&server_addr)?
     .run()
     .await
 }
+
+/// A commit identity, if both its name and email are configured. Warns when only one is set.
+fn identity(
+    role: &str,
+    name: &Option<String>,
+    email: &Option<String>,
+) -> Option<CommitIdentity> {
+    match (name, email) {
+        (Some(name), Some(email)) => Some(CommitIdentity {
+            name: name.clone(),
+            email: email.clone(),
+        }),
+        (None, None) => None,
+        _ => {
+            log::warn!("Commit {} needs both a name and an email; ignoring it", role);
+            None
+        }
+    }
+}
+
+/// Create the configured README publisher.
+fn build_publisher(config: &config::Config) -> Arc<dyn ReadmePublisher> {
+    let commit = CommitSettings {
+        message_template: config
+            .commit_message_template
+            .clone()
+            .unwrap_or_else(|| DEFAULT_COMMIT_TEMPLATE.to_string()),
+        author: identity("author", &config.commit_author_name, &config.commit_author_email),
+        committer: identity(
+            "committer",
+            &config.commit_committer_name,
+            &config.commit_committer_email,
+        ),
+        include_fen: config.commit_include_fen,
+        include_pgn: config.commit_include_pgn,
+    };
+    match config.publisher.as_str() {
+        "github" => Arc::new(github_publisher(config, commit)),
+        "gitlab" => Arc::new(GitlabPublisher::new(GitlabConfig {
+            token: config.gitlab_token.clone().expect("checked by Config::from_env"),
+            api_url: format!("{}/api/v4", config.gitlab_url),
+            project: config.gitlab_project.clone().expect("checked by Config::from_env"),
+            readme_path: config.readme_path.clone(),
+            branch: config.readme_branch.clone(),
+            commit,
+        })),
+        "gitea" => Arc::new(GiteaPublisher::new(GiteaConfig {
+            token: config.gitea_token.clone().expect("checked by Config::from_env"),
+            api_url: format!(
+                "{}/api/v1",
+                config.gitea_url.as_deref().expect("checked by Config::from_env")
+            ),
+            owner_repo: config.gitea_repo.clone().expect("checked by Config::from_env"),
+            readme_path: config.readme_path.clone(),
+            branch: config.readme_branch.clone(),
+            commit,
+        })),
+        "local-git" => Arc::new(LocalGitPublisher::new(LocalGitConfig {
+            repo_path: config
+                .local_git_repo
+                .clone()
+                .expect("checked by Config::from_env")
+                .into(),
+            readme_path: config.readme_path.clone(),
+            remote: config.local_git_remote.clone(),
+            branch: config.readme_branch.clone(),
+            commit,
+        })),
+        "file" => Arc::new(FilePublisher::new(FileConfig {
+            path: config.preview_file.clone().into(),
+            commit,
+        })),
+        other => unreachable!("PUBLISHER {} is checked by Config::from_env", other),
+    }
+}
+
+/// Create a publisher committing through the GitHub API.
+fn github_publisher(config: &config::Config, commit: CommitSettings) -> GithubService {
+    // Prefer GitHub App credentials over the personal access token when both are configured
+    let github_auth = match (
+        &config.github_app_id,
+        &config.github_app_private_key_path,
+        config.github_app_installation_id,
+    ) {
+        (Some(app_id), Some(key_path), Some(installation_id)) => {
+            let pem = std::fs::read(key_path).expect("Failed to read GitHub App private key");
+            let app = GithubAppAuth::new(
+                app_id.clone(),
+                &pem,
+                installation_id,
+                config.github_api_url.clone(),
+            )
+            .expect("Failed to load GitHub App credentials");
+            GithubAuth::App(app)
+        }
+        _ => GithubAuth::Token(config.github_token.clone().expect("checked by Config::from_env")),
+    };
+    let publish_method = match config.github_publish_method.as_str() {
+        "git-data" => PublishMethod::GitData,
+        "contents" => PublishMethod::Contents,
+        other => unreachable!("GITHUB_PUBLISH_METHOD {} is checked by Config::from_env", other),
+    };
+    let targets = config
+        .github_targets
+        .iter()
+        .map(|target| {
+            GithubTarget::from_config(target)
+                .unwrap_or_else(|e| panic!("Invalid GitHub target {}: {:#}", target.repo, e))
+        })
+        .collect();
+    let github_config = Arc::new(GithubConfig {
+        auth: github_auth,
+        api_url: format!("{}/repos", config.github_api_url),
+        targets,
+        commit,
+        method: publish_method,
+    });
+    GithubService::new(github_config)
+}
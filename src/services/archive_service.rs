@@ -0,0 +1,218 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Name of the metadata index inside the archive directory (one JSON object per line).
+const INDEX_FILE: &str = "index.jsonl";
+/// Name of the log of attributed moves inside the archive directory.
+const MOVES_FILE: &str = "moves.jsonl";
+
+/// Number of attributed moves kept in memory for `recent_moves`.
+const RECENT_MOVES_KEPT: usize = 100;
+
+/// Player name recorded for the engine's side.
+pub const ENGINE_PLAYER: &str = "Stockfish";
+
+/// Metadata of a game stored in the archive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedGame {
+    pub id: u64,
+    /// Unix timestamps (seconds) of the start and end of the game
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub white: String,
+    pub black: String,
+    /// Score in PGN notation, or "*" for a game abandoned by starting a new one
+    pub result: String,
+    pub reason: String,
+    /// Number of half-moves played
+    pub plies: usize,
+    /// Starting position if the game did not start from the standard position
+    pub start_fen: Option<String>,
+    /// GitHub logins of the signed-in visitors who moved in the game
+    #[serde(default)]
+    pub players: Vec<String>,
+}
+
+/// A move played by a signed-in visitor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveRecord {
+    pub login: String,
+    /// Move in UCI notation
+    pub uci: String,
+    /// Unix timestamp (seconds) of the move
+    pub at: u64,
+}
+
+/// All-time standing of a visitor on the leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LeaderboardEntry {
+    pub login: String,
+    pub moves: usize,
+    /// Games against the engine won by White in which the visitor moved
+    pub wins: usize,
+}
+
+/// File-backed archive of finished and abandoned games.
+///
+/// Each game is stored as `<id>.pgn`, with its metadata appended to `index.jsonl`. The index,
+/// the leaderboard totals and the latest moves are read once in `open` and then kept up to
+/// date in memory, so rendering the README does not re-read the archive.
+pub struct ArchiveService {
+    dir: PathBuf,
+    state: Mutex<ArchiveState>,
+}
+
+/// In-memory view of the archive files.
+#[derive(Default)]
+struct ArchiveState {
+    /// All archived games, most recent first
+    games: Vec<ArchivedGame>,
+    /// Running leaderboard totals by login
+    totals: HashMap<String, LeaderboardEntry>,
+    /// Latest attributed moves, oldest first
+    recent_moves: VecDeque<MoveRecord>,
+}
+
+impl ArchiveState {
+    fn add_game(&mut self, game: ArchivedGame) {
+        if game.result == "1-0" && game.black == ENGINE_PLAYER {
+            for login in &game.players {
+                entry(&mut self.totals, login).wins += 1;
+            }
+        }
+        self.games.insert(0, game);
+    }
+
+    fn add_move(&mut self, record: MoveRecord) {
+        entry(&mut self.totals, &record.login).moves += 1;
+        if self.recent_moves.len() == RECENT_MOVES_KEPT {
+            self.recent_moves.pop_front();
+        }
+        self.recent_moves.push_back(record);
+    }
+}
+
+impl ArchiveService {
+    /// Open (and create if needed) the archive directory, loading its index and move log.
+    pub fn open<P: Into<PathBuf>>(dir: P) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let mut games: Vec<ArchivedGame> = read_lines(&dir.join(INDEX_FILE))?;
+        games.sort_by_key(|g| g.id);
+        let mut state = ArchiveState::default();
+        for game in games {
+            state.add_game(game);
+        }
+        for record in read_lines(&dir.join(MOVES_FILE))? {
+            state.add_move(record);
+        }
+        Ok(Self {
+            dir,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Store a game, assigning it the next id. The `id` of `game` is ignored.
+    pub fn store(&self, mut game: ArchivedGame, pgn: &str) -> io::Result<ArchivedGame> {
+        let mut state = self.state();
+        game.id = state.games.first().map_or(1, |last| last.id + 1);
+        fs::write(self.pgn_path(game.id), pgn)?;
+        append_line(&self.dir.join(INDEX_FILE), &game)?;
+        state.add_game(game.clone());
+        Ok(game)
+    }
+
+    /// Record a move played by a signed-in visitor.
+    pub fn record_move(&self, record: &MoveRecord) -> io::Result<()> {
+        let mut state = self.state();
+        append_line(&self.dir.join(MOVES_FILE), record)?;
+        state.add_move(record.clone());
+        Ok(())
+    }
+
+    /// The `n` most recent attributed moves (at most 100), most recent first.
+    pub fn recent_moves(&self, n: usize) -> io::Result<Vec<MoveRecord>> {
+        Ok(self.state().recent_moves.iter().rev().take(n).cloned().collect())
+    }
+
+    /// The top `n` visitors by games won against the engine, then by moves played.
+    pub fn leaderboard(&self, n: usize) -> io::Result<Vec<LeaderboardEntry>> {
+        let mut board: Vec<LeaderboardEntry> = self.state().totals.values().cloned().collect();
+        board.sort_by(|a, b| {
+            (b.wins, b.moves)
+                .cmp(&(a.wins, a.moves))
+                .then_with(|| a.login.cmp(&b.login))
+        });
+        board.truncate(n);
+        Ok(board)
+    }
+
+    /// All archived games, most recent first.
+    pub fn list(&self) -> io::Result<Vec<ArchivedGame>> {
+        Ok(self.state().games.clone())
+    }
+
+    /// The `n` most recent games.
+    pub fn recent(&self, n: usize) -> io::Result<Vec<ArchivedGame>> {
+        Ok(self.state().games.iter().take(n).cloned().collect())
+    }
+
+    /// PGN of an archived game.
+    pub fn pgn(&self, id: u64) -> io::Result<String> {
+        fs::read_to_string(self.pgn_path(id))
+    }
+
+    fn pgn_path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{}.pgn", id))
+    }
+
+    /// Lock the in-memory state, which stays consistent even if a holder panicked.
+    fn state(&self) -> MutexGuard<'_, ArchiveState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Leaderboard entry for a login, created empty on first use.
+fn entry<'a>(
+    entries: &'a mut HashMap<String, LeaderboardEntry>,
+    login: &str,
+) -> &'a mut LeaderboardEntry {
+    entries
+        .entry(login.to_string())
+        .or_insert_with(|| LeaderboardEntry {
+            login: login.to_string(),
+            moves: 0,
+            wins: 0,
+        })
+}
+
+/// Append a value to a JSON-lines file.
+fn append_line<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(value)?)
+}
+
+/// Read all values from a JSON-lines file (empty if missing), skipping malformed lines.
+fn read_lines<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                log::warn!("Skipping malformed archive entry in {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
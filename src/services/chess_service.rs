@@ -1,9 +1,174 @@
-use crate::services::engine_service::EngineService;
+use crate::services::archive_service::{ArchiveService, ArchivedGame, ENGINE_PLAYER, MoveRecord};
+use crate::services::engine_service::{EngineService, Score, SearchInfo};
+use crate::utils::chess960;
+use crate::utils::dates;
+use crate::utils::eco::{self, Opening};
+use crate::utils::pgn;
+use crate::utils::polyglot::PolyglotBook;
+use crate::utils::positions;
+use crate::utils::puzzles::{Puzzle, PuzzleSet};
+use crate::utils::tablebase::{Tablebase, TablebaseProbe, Wdl};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// FEN of the standard starting position.
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Centipawn margin by which the engine must consider itself worse to accept a draw offer.
+const DRAW_ACCEPT_MARGIN_CP: i32 = 50;
+
+/// Final outcome of a game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+/// Result of a finished game, with a human-readable reason.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub reason: String,
+}
+
+impl GameResult {
+    /// Create a result with the given outcome and reason.
+    pub fn new(outcome: Outcome, reason: &str) -> Self {
+        Self {
+            outcome,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Score in PGN notation (e.g., "1-0").
+    pub fn score(&self) -> &'static str {
+        match self.outcome {
+            Outcome::WhiteWins => "1-0",
+            Outcome::BlackWins => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        }
+    }
+}
+
+/// Pieces captured so far by each side, plus the current material balance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapturedPieces {
+    /// Black pieces captured by White (lowercase FEN letters), most valuable first
+    pub by_white: Vec<char>,
+    /// White pieces captured by Black (uppercase FEN letters), most valuable first
+    pub by_black: Vec<char>,
+    /// Material on the board in pawns, positive when White is ahead
+    pub balance: i32,
+}
+
+/// Who answers the visitors' moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Opponent {
+    /// Visitors play White against the engine
+    #[default]
+    Engine,
+    /// Visitors play both sides, taking turns
+    Human,
+}
+
+/// What happens when the side to move runs out of time on the move clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeoutAction {
+    /// The side to move loses on time
+    #[default]
+    Forfeit,
+    /// The engine plays a move for the idle side
+    EngineMove,
+}
+
+/// Remaining time on a correspondence-style clock with a fixed budget per move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockState {
+    pub white: Duration,
+    pub black: Duration,
+    /// Time each side has for a move
+    pub per_move: Duration,
+    pub white_to_move: bool,
+}
+
+/// State of the puzzle being solved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PuzzleOutcome {
+    InProgress,
+    Solved,
+    Failed,
+}
+
+/// Progress on the current puzzle, with the player's solving streak.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleProgress {
+    pub id: String,
+    pub outcome: PuzzleOutcome,
+    /// Puzzles solved in a row (the first attempt at each puzzle counts)
+    pub streak: u32,
+}
+
+/// Puzzle currently on the board.
+struct ActivePuzzle {
+    puzzle: Puzzle,
+    /// Number of solution moves played so far
+    ply: usize,
+    outcome: PuzzleOutcome,
+}
 
 /// Service for managing chess game state and player/engine moves.
 pub struct ChessService {
     engine: EngineService,
     selected_square: Option<String>,
+    /// FEN of every position reached in the current game, starting position first
+    positions: Vec<String>,
+    result: Option<GameResult>,
+    /// One-off message for the next render (e.g., a declined draw offer)
+    notice: Option<String>,
+    /// Engine's view after its last reply: score from White's point of view,
+    /// pv continuing from the current position
+    analysis: Option<SearchInfo>,
+    /// Suggested move for the player, shown until the next move
+    hint: Option<String>,
+    /// Number of hints requested in the current game
+    hints_used: u32,
+    /// Opening book consulted before searching for the engine's reply
+    book: Option<PolyglotBook>,
+    /// Whether the engine's last reply came from the opening book
+    in_book: bool,
+    /// Most specific named opening matching the game's moves
+    opening: Option<Opening>,
+    /// Syzygy tablebases used to adjudicate simplified endgames
+    tablebase: Option<Tablebase>,
+    /// Tablebase value of the current position, if covered
+    tablebase_probe: Option<TablebaseProbe>,
+    /// Chess960 position number when playing Fischer Random
+    chess960_id: Option<u32>,
+    /// Name of the custom starting position (e.g., "Lucena position"), if any
+    start_name: Option<String>,
+    /// Who plays Black; kept across new games until changed
+    opponent: Opponent,
+    /// Pending draw offer in human-vs-human games: `Some(true)` if White offered
+    draw_offer: Option<bool>,
+    /// Time budget per move, if the game is played on a clock
+    move_time_limit: Option<Duration>,
+    timeout_action: TimeoutAction,
+    /// Game start followed by the time of every move played
+    move_times: Vec<SystemTime>,
+    /// Archive receiving finished and abandoned games
+    archive: Option<Arc<ArchiveService>>,
+    /// Whether the current game has been stored in the archive
+    archived: bool,
+    /// GitHub logins of the signed-in visitors who moved in the current game
+    players: Vec<String>,
+    /// Puzzles for the daily puzzle mode
+    puzzles: Option<PuzzleSet>,
+    /// Puzzle being solved instead of playing the engine
+    puzzle: Option<ActivePuzzle>,
+    puzzle_streak: u32,
+    /// Id of the last puzzle that counted towards the streak
+    last_scored_puzzle: Option<String>,
 }
 
 impl ChessService {
@@ -12,30 +177,135 @@ impl ChessService {
         Self {
             engine,
             selected_square: None,
+            positions: vec![STARTING_FEN.to_string()],
+            result: None,
+            notice: None,
+            analysis: None,
+            hint: None,
+            hints_used: 0,
+            book: None,
+            in_book: false,
+            opening: None,
+            tablebase: None,
+            tablebase_probe: None,
+            chess960_id: None,
+            start_name: None,
+            opponent: Opponent::Engine,
+            draw_offer: None,
+            move_time_limit: None,
+            timeout_action: TimeoutAction::Forfeit,
+            move_times: vec![SystemTime::now()],
+            archive: None,
+            archived: false,
+            players: Vec::new(),
+            puzzles: None,
+            puzzle: None,
+            puzzle_streak: 0,
+            last_scored_puzzle: None,
         }
     }
 
-    /// Play a move as the player, then let the engine reply.
+    /// Let the engine reply from an opening book while the position is covered by it.
+    pub fn with_opening_book(mut self, book: PolyglotBook) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    /// Adjudicate positions covered by the given Syzygy tablebases.
+    pub fn with_tablebase(mut self, tablebase: Tablebase) -> Self {
+        self.tablebase = Some(tablebase);
+        self
+    }
+
+    /// Store finished games, and games abandoned for a new one, in the archive.
+    pub fn with_archive(mut self, archive: Arc<ArchiveService>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Enable the daily puzzle mode with the given puzzles.
+    pub fn with_puzzles(mut self, puzzles: PuzzleSet) -> Self {
+        self.puzzles = Some(puzzles);
+        self
+    }
+
+    /// Play on a clock giving each side `limit` of wall-clock time per move.
+    pub fn with_move_clock(mut self, limit: Duration, on_timeout: TimeoutAction) -> Self {
+        self.move_time_limit = Some(limit);
+        self.timeout_action = on_timeout;
+        self
+    }
+
+    /// Choose who answers the visitors' moves from the next game on.
+    pub fn set_opponent(&mut self, opponent: Opponent) {
+        self.opponent = opponent;
+    }
+
+    /// Play a move for the side to move, then let the engine reply when playing against it.
     pub async fn play(&mut self, mv: &str) -> Result<(), String> {
+        self.play_by(mv, None).await
+    }
+
+    /// Play a move on behalf of a signed-in visitor (if any), attributing it in the archive.
+    pub async fn play_by(&mut self, mv: &str, player: Option<&str>) -> Result<(), String> {
+        if self.result.is_some() {
+            return Err("Game is already over".to_string());
+        }
+        if self
+            .puzzle
+            .as_ref()
+            .is_some_and(|p| p.outcome != PuzzleOutcome::InProgress)
+        {
+            return Err("Puzzle is already finished".to_string());
+        }
+        self.notice = None;
+        self.hint = None;
         // Validate move
+        let mv = mv.to_ascii_lowercase();
+        let mv = mv.as_str();
         let valid_moves = self.engine.get_valid_moves().await.map_err(|e| e.to_string())?;
+        validate_promotion(mv, &valid_moves)?;
         if !valid_moves.contains(&mv.to_string()) {
             return Err(format!("Invalid move: {}", mv));
         }
         // Player move
-        self.engine.make_move(mv).await.map_err(|e| e.to_string())?;
-        // Engine reply
-        let engine_move = self.engine.best_move().await.map_err(|e| e.to_string())?;
-        if !engine_move.is_empty() {
-            self.engine.make_move(&engine_move).await.map_err(|e| e.to_string())?;
+        let white_moved = self.is_white_to_move();
+        self.make_move(mv).await?;
+        if let Some(login) = player {
+            self.attribute_move(login, mv);
+        }
+        let mover_wins = if white_moved {
+            Outcome::WhiteWins
+        } else {
+            Outcome::BlackWins
+        };
+        self.check_game_over(mover_wins).await?;
+        if self.puzzle.is_none() {
+            self.adjudicate_tablebase();
+        }
+        // Moving declines the opponent's pending draw offer
+        if self.draw_offer == Some(!white_moved) {
+            self.draw_offer = None;
+        }
+        // Opponent reply: from the puzzle solution, or the engine
+        if self.puzzle.is_some() {
+            self.puzzle_reply(mv).await?;
+        } else if self.opponent == Opponent::Engine && self.result.is_none() {
+            self.engine_reply().await?;
+        }
+        if self.is_standard_start() {
+            self.opening = eco::lookup(self.engine.moves()).cloned();
         }
         // Clear selection after move
         self.selected_square = None;
         Ok(())
     }
 
-    /// Toggle selection of a square (for piece selection UI).
+    /// Toggle selection of a square (for piece selection UI). Only squares a1-h8 are accepted.
     pub async fn select(&mut self, square: &str) -> Result<(), String> {
+        if square.parse::<shakmaty::Square>().is_err() {
+            return Err(format!("Invalid square: {}", square));
+        }
         if self.selected_square.as_deref() == Some(square) {
             self.selected_square = None;
         } else {
@@ -44,20 +314,314 @@ impl ChessService {
         Ok(())
     }
 
+    /// Ask the engine for the player's best move at the given depth, without playing it.
+    pub async fn hint(&mut self, depth: u32) -> Result<String, String> {
+        if self.result.is_some() {
+            return Err("Game is already over".to_string());
+        }
+        let mv = self
+            .engine
+            .best_move_at_depth(depth)
+            .await
+            .map_err(|e| e.to_string())?;
+        self.hint = Some(mv.clone());
+        self.hints_used += 1;
+        Ok(mv)
+    }
+
+    /// Resign the game on behalf of the player. In puzzle mode this gives up the puzzle.
+    pub async fn resign(&mut self) -> Result<(), String> {
+        if self.result.is_some() {
+            return Err("Game is already over".to_string());
+        }
+        if let Some(active) = &self.puzzle {
+            if active.outcome != PuzzleOutcome::InProgress {
+                return Err("Puzzle is already finished".to_string());
+            }
+            if let Some(expected) = active.puzzle.solution.get(active.ply) {
+                self.notice = Some(format!("Gave up: the solution was {}.", expected));
+            }
+            self.finish_puzzle(PuzzleOutcome::Failed);
+            self.selected_square = None;
+            return Ok(());
+        }
+        self.finish(if self.is_white_to_move() {
+            GameResult::new(Outcome::BlackWins, "White resigns")
+        } else {
+            GameResult::new(Outcome::WhiteWins, "Black resigns")
+        });
+        self.selected_square = None;
+        Ok(())
+    }
+
+    /// Claim a draw by threefold repetition or the fifty-move rule, or offer one to the engine.
+    ///
+    /// Returns whether the game ended in a draw.
+    pub async fn draw(&mut self) -> Result<bool, String> {
+        if self.result.is_some() {
+            return Err("Game is already over".to_string());
+        }
+        if self.puzzle.is_some() {
+            return Err("Draw offers are not available in puzzle mode".to_string());
+        }
+        self.notice = None;
+        let reason = if self.is_threefold_repetition() {
+            "Threefold repetition"
+        } else if self.is_fifty_move_rule() {
+            "Fifty-move rule"
+        } else if self.opponent == Opponent::Human {
+            let white = self.is_white_to_move();
+            if self.draw_offer == Some(!white) {
+                "Draw agreed"
+            } else {
+                self.draw_offer = Some(white);
+                self.notice = Some(format!(
+                    "{} offers a draw; {} can accept with Draw before moving.",
+                    side_name(white),
+                    side_name(!white)
+                ));
+                return Ok(false);
+            }
+        } else if self.engine_accepts_draw().await? {
+            "Draw agreed"
+        } else {
+            self.notice = Some("The engine declined the draw offer.".to_string());
+            return Ok(false);
+        };
+        self.finish(GameResult::new(Outcome::Draw, reason));
+        self.selected_square = None;
+        Ok(true)
+    }
+
     /// Start a new game (reset engine and selection).
     pub async fn new_game(&mut self) -> Result<(), String> {
-        self.engine.new_game().await.map_err(|e| e.to_string())?;
-        self.selected_square = None;
+        self.start_game(None, None).await
+    }
+
+    /// Start a new Chess960 game from the given position number (0-959), or a random one.
+    ///
+    /// Returns the position number played.
+    pub async fn new_chess960_game(&mut self, id: Option<u32>) -> Result<u32, String> {
+        let id = id.unwrap_or_else(chess960::random_id);
+        let fen = chess960::start_fen(id).ok_or_else(|| {
+            format!(
+                "Invalid Chess960 position {}: expected 0-{}",
+                id,
+                chess960::POSITION_COUNT - 1
+            )
+        })?;
+        self.start_game(Some(fen), Some(id)).await?;
+        Ok(id)
+    }
+
+    /// Start a new game from a FEN. The engine moves first if it is Black to move.
+    pub async fn new_game_from_fen(&mut self, fen: &str) -> Result<(), String> {
+        let fen = positions::validate_fen(fen)?;
+        let black_to_move = fen.split_whitespace().nth(1) == Some("b");
+        self.start_game(Some(fen), None).await?;
+        // Set before the engine replies so the drill is not adjudicated
+        self.start_name = Some("Custom position".to_string());
+        if black_to_move && self.opponent == Opponent::Engine {
+            self.engine_reply().await?;
+        }
         Ok(())
     }
 
+    /// Start a new game from a named position in the catalog (e.g., "lucena").
+    pub async fn new_game_from_named(&mut self, key: &str) -> Result<(), String> {
+        let position = positions::find(key).ok_or_else(|| {
+            let known: Vec<&str> = positions::CATALOG.iter().map(|p| p.key).collect();
+            format!("Unknown position '{}': expected one of {}", key, known.join(", "))
+        })?;
+        self.new_game_from_fen(position.fen).await?;
+        self.start_name = Some(position.name.to_string());
+        Ok(())
+    }
+
+    /// Start today's puzzle from the configured puzzle file.
+    ///
+    /// Returns the puzzle id.
+    pub async fn new_daily_puzzle(&mut self) -> Result<String, String> {
+        let day = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| e.to_string())?
+            .as_secs()
+            / 86_400;
+        let puzzle = self
+            .puzzles
+            .as_ref()
+            .ok_or_else(|| "Puzzle mode is not configured".to_string())?
+            .daily(day)
+            .clone();
+        self.start_puzzle(puzzle).await
+    }
+
+    /// Start solving the given puzzle. Returns the puzzle id.
+    pub async fn start_puzzle(&mut self, puzzle: Puzzle) -> Result<String, String> {
+        self.start_game(Some(puzzle.fen.clone()), None).await?;
+        let id = puzzle.id.clone();
+        self.puzzle = Some(ActivePuzzle {
+            puzzle,
+            ply: 0,
+            outcome: PuzzleOutcome::InProgress,
+        });
+        Ok(id)
+    }
+
+    /// Get the remaining time per side at `now`, if the game is played on a clock and still running.
+    ///
+    /// The clock only starts with the first move, so an untouched board never runs out of time.
+    pub fn get_clock(&self, now: SystemTime) -> Option<ClockState> {
+        let per_move = self.move_time_limit?;
+        if self.result.is_some() || self.puzzle.is_some() {
+            return None;
+        }
+        let turn_start = match self.move_times.as_slice() {
+            [_, .., last] => *last,
+            _ => now,
+        };
+        let elapsed = now.duration_since(turn_start).unwrap_or_default();
+        let remaining = per_move.saturating_sub(elapsed);
+        let white_to_move = self.is_white_to_move();
+        Some(ClockState {
+            white: if white_to_move { remaining } else { per_move },
+            black: if white_to_move { per_move } else { remaining },
+            per_move,
+            white_to_move,
+        })
+    }
+
+    /// Act on the clock if the side to move has run out of time at `now`: either
+    /// adjudicate a loss on time or let the engine move for the idle side.
+    ///
+    /// Returns whether the game changed.
+    pub async fn check_clock(&mut self, now: SystemTime) -> Result<bool, String> {
+        let Some(clock) = self.get_clock(now) else {
+            return Ok(false);
+        };
+        let remaining = if clock.white_to_move {
+            clock.white
+        } else {
+            clock.black
+        };
+        if !remaining.is_zero() {
+            return Ok(false);
+        }
+        let side = side_name(clock.white_to_move);
+        match self.timeout_action {
+            TimeoutAction::Forfeit => {
+                let outcome = if clock.white_to_move {
+                    Outcome::BlackWins
+                } else {
+                    Outcome::WhiteWins
+                };
+                let reason = format!("{} lost on time", side);
+                self.finish(GameResult::new(outcome, &reason));
+                self.selected_square = None;
+            }
+            TimeoutAction::EngineMove => {
+                let mv = self.engine.best_move().await.map_err(|e| e.to_string())?;
+                self.play(&mv).await?;
+                self.notice = Some(format!(
+                    "{} ran out of time; the engine played {} instead.",
+                    side, mv
+                ));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Get the game archive, if one is configured.
+    pub fn get_archive(&self) -> Option<&ArchiveService> {
+        self.archive.as_deref()
+    }
+
+    /// Get who answers the visitors' moves.
+    pub fn get_opponent(&self) -> Opponent {
+        self.opponent
+    }
+
+    /// Get the progress on the current puzzle, if in puzzle mode.
+    pub fn get_puzzle(&self) -> Option<PuzzleProgress> {
+        self.puzzle.as_ref().map(|active| PuzzleProgress {
+            id: active.puzzle.id.clone(),
+            outcome: active.outcome,
+            streak: self.puzzle_streak,
+        })
+    }
+
+    /// Get the Chess960 position number, if this is a Chess960 game.
+    pub fn get_chess960_id(&self) -> Option<u32> {
+        self.chess960_id
+    }
+
+    /// Get the name of the custom starting position, if the game did not start normally.
+    pub fn get_start_name(&self) -> Option<&str> {
+        self.start_name.as_deref()
+    }
+
     /// Get the current FEN string for the board.
     pub async fn get_fen(&mut self) -> Result<String, String> {
         self.engine.get_position().await.map_err(|e| e.to_string())
     }
 
-    /// Get the list of valid moves in the current position.
+    /// Get the squares of the pieces giving check to the side to move.
+    pub async fn get_checkers(&mut self) -> Result<Vec<String>, String> {
+        let info = self.engine.describe().await.map_err(|e| e.to_string())?;
+        Ok(info.checkers)
+    }
+
+    /// Get the pieces captured by each side, derived from the position history.
+    pub fn get_captured(&self) -> CapturedPieces {
+        let mut captured = CapturedPieces::default();
+        for pair in self.positions.windows(2) {
+            let (before, after) = (&pair[0], &pair[1]);
+            let white_moved = before.split_whitespace().nth(1) == Some("w");
+            for piece in "pnbrqPNBRQ".chars() {
+                // Only the side that did not move can lose pieces (promotions change the mover's)
+                if piece.is_lowercase() != white_moved {
+                    continue;
+                }
+                let lost = count_piece(before, piece).saturating_sub(count_piece(after, piece));
+                let target = if white_moved {
+                    &mut captured.by_white
+                } else {
+                    &mut captured.by_black
+                };
+                target.extend(std::iter::repeat_n(piece, lost));
+            }
+        }
+        captured
+            .by_white
+            .sort_by_key(|&p| std::cmp::Reverse(piece_value(p)));
+        captured
+            .by_black
+            .sort_by_key(|&p| std::cmp::Reverse(piece_value(p)));
+        if let Some(fen) = self.positions.last() {
+            let placement = fen.split_whitespace().next().unwrap_or_default();
+            captured.balance = placement
+                .chars()
+                .map(|c| {
+                    if c.is_uppercase() {
+                        piece_value(c)
+                    } else {
+                        -piece_value(c)
+                    }
+                })
+                .sum();
+        }
+        captured
+    }
+
+    /// Get the list of valid moves in the current position (empty once the game or puzzle is over).
     pub async fn get_valid_moves(&mut self) -> Result<Vec<String>, String> {
+        let puzzle_over = self
+            .puzzle
+            .as_ref()
+            .is_some_and(|p| p.outcome != PuzzleOutcome::InProgress);
+        if self.result.is_some() || puzzle_over {
+            return Ok(Vec::new());
+        }
         self.engine.get_valid_moves().await.map_err(|e| e.to_string())
     }
 
@@ -65,4 +629,418 @@ impl ChessService {
     pub fn get_selected_square(&self) -> Option<&str> {
         self.selected_square.as_deref()
     }
+
+    /// Get the result of the game, if it has finished.
+    pub fn get_result(&self) -> Option<&GameResult> {
+        self.result.as_ref()
+    }
+
+    /// Get the engine's evaluation (White's point of view) and expected line after its last reply.
+    pub fn get_analysis(&self) -> Option<&SearchInfo> {
+        self.analysis.as_ref()
+    }
+
+    /// Get the suggested move from the last hint, if it is still current.
+    pub fn get_hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    /// Get the number of hints requested in the current game.
+    pub fn get_hints_used(&self) -> u32 {
+        self.hints_used
+    }
+
+    /// Whether the engine's last reply was taken from the opening book.
+    pub fn is_in_book(&self) -> bool {
+        self.in_book
+    }
+
+    /// Get the named opening (ECO) reached by the game's moves, if any.
+    pub fn get_opening(&self) -> Option<&Opening> {
+        self.opening.as_ref()
+    }
+
+    /// Get the tablebase value of the current position, if covered.
+    pub fn get_tablebase_probe(&self) -> Option<&TablebaseProbe> {
+        self.tablebase_probe.as_ref()
+    }
+
+    /// Get the one-off notice for the next render, if any.
+    pub fn get_notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+
+    /// Get the moves played so far in SAN.
+    pub fn get_san_moves(&self) -> Vec<String> {
+        pgn::san_moves(
+            self.custom_start_fen(),
+            self.chess960_id.is_some(),
+            self.engine.moves(),
+        )
+    }
+
+    /// Get the game so far as PGN, with "*" while it is still in progress.
+    pub fn get_pgn(&self) -> String {
+        self.pgn_with_result(self.result.as_ref().map_or("*", GameResult::score))
+    }
+
+    //–– Internal helpers ––
+
+    /// Reset the engine and all per-game state, starting from the given position.
+    async fn start_game(
+        &mut self,
+        start_fen: Option<String>,
+        chess960_id: Option<u32>,
+    ) -> Result<(), String> {
+        if !self.archived && self.puzzle.is_none() && !self.engine.moves().is_empty() {
+            self.archive_game("*", "Abandoned");
+        }
+        self.engine.new_game().await.map_err(|e| e.to_string())?;
+        let chess960 = if chess960_id.is_some() { "true" } else { "false" };
+        self.engine
+            .set_option("UCI_Chess960", chess960)
+            .await
+            .map_err(|e| e.to_string())?;
+        if start_fen.is_some() {
+            self.engine
+                .set_start_position(start_fen.clone())
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        self.selected_square = None;
+        self.positions = vec![start_fen.unwrap_or_else(|| STARTING_FEN.to_string())];
+        self.result = None;
+        self.notice = None;
+        self.analysis = None;
+        self.hint = None;
+        self.hints_used = 0;
+        self.in_book = false;
+        self.opening = None;
+        self.tablebase_probe = None;
+        self.chess960_id = chess960_id;
+        self.start_name = None;
+        self.puzzle = None;
+        self.draw_offer = None;
+        self.move_times = vec![SystemTime::now()];
+        self.archived = false;
+        self.players.clear();
+        Ok(())
+    }
+
+    /// End the game with the given result and archive it.
+    fn finish(&mut self, result: GameResult) {
+        if self.puzzle.is_none() {
+            self.archive_game(result.score(), &result.reason);
+        }
+        self.result = Some(result);
+    }
+
+    /// Store the current game in the archive, if one is configured.
+    /// Archive failures are logged rather than interrupting the game.
+    fn archive_game(&mut self, result: &str, reason: &str) {
+        let Some(archive) = &self.archive else {
+            return;
+        };
+        let game = ArchivedGame {
+            id: 0,
+            started_at: self.started_at(),
+            ended_at: unix_secs(SystemTime::now()),
+            white: "Visitors".to_string(),
+            black: self.black_name().to_string(),
+            result: result.to_string(),
+            reason: reason.to_string(),
+            plies: self.engine.moves().len(),
+            start_fen: self.custom_start_fen().map(str::to_string),
+            players: self.players.clone(),
+        };
+        let pgn = self.pgn_with_result(result);
+        match archive.store(game, &pgn) {
+            Ok(game) => log::info!("Archived game {}", game.id),
+            Err(e) => log::error!("Failed to archive game: {}", e),
+        }
+        self.archived = true;
+    }
+
+    /// Render the game so far as PGN with the given result.
+    fn pgn_with_result(&self, result: &str) -> String {
+        let (year, month, day) = dates::utc_date(self.started_at());
+        let tags = [
+            ("Event", "README Chess".to_string()),
+            ("Date", format!("{:04}.{:02}.{:02}", year, month, day)),
+            ("White", "Visitors".to_string()),
+            ("Black", self.black_name().to_string()),
+        ];
+        pgn::write_pgn(
+            &tags,
+            self.custom_start_fen(),
+            self.chess960_id.is_some(),
+            self.engine.moves(),
+            result,
+        )
+    }
+
+    /// When the current game started, in Unix seconds.
+    fn started_at(&self) -> u64 {
+        self.move_times.first().map_or(0, |&t| unix_secs(t))
+    }
+
+    /// Name of the player on the Black side.
+    fn black_name(&self) -> &'static str {
+        match self.opponent {
+            Opponent::Engine => ENGINE_PLAYER,
+            Opponent::Human => "Visitors",
+        }
+    }
+
+    /// Starting FEN, unless the game began from the standard position.
+    fn custom_start_fen(&self) -> Option<&str> {
+        self.positions
+            .first()
+            .map(String::as_str)
+            .filter(|fen| *fen != STARTING_FEN)
+    }
+
+    /// Credit a signed-in visitor with a move, in the game's players and the archive's move log.
+    fn attribute_move(&mut self, login: &str, mv: &str) {
+        if !self.players.iter().any(|p| p == login) {
+            self.players.push(login.to_string());
+        }
+        let Some(archive) = &self.archive else {
+            return;
+        };
+        let record = MoveRecord {
+            login: login.to_string(),
+            uci: mv.to_string(),
+            at: unix_secs(SystemTime::now()),
+        };
+        if let Err(e) = archive.record_move(&record) {
+            log::error!("Failed to record move by {}: {}", login, e);
+        }
+    }
+
+    /// Whether it is White's turn in the current position.
+    fn is_white_to_move(&self) -> bool {
+        self.positions
+            .last()
+            .and_then(|fen| fen.split_whitespace().nth(1))
+            != Some("b")
+    }
+
+    /// Whether the game started from the standard position with standard castling rules.
+    fn is_standard_start(&self) -> bool {
+        self.chess960_id.is_none() && self.positions.first().map(String::as_str) == Some(STARTING_FEN)
+    }
+
+    /// Apply a move on the engine and record the resulting position.
+    async fn make_move(&mut self, mv: &str) -> Result<(), String> {
+        self.engine.make_move(mv).await.map_err(|e| e.to_string())?;
+        let fen = self.engine.get_position().await.map_err(|e| e.to_string())?;
+        self.positions.push(fen);
+        self.move_times.push(SystemTime::now());
+        Ok(())
+    }
+
+    /// Check the player's move against the puzzle solution and play the opponent's answer.
+    /// Any move that delivers checkmate also solves the puzzle.
+    async fn puzzle_reply(&mut self, mv: &str) -> Result<(), String> {
+        let Some(active) = self.puzzle.as_mut() else {
+            return Ok(());
+        };
+        let expected = active.puzzle.solution[active.ply].clone();
+        let mated = self
+            .result
+            .as_ref()
+            .is_some_and(|r| r.outcome == Outcome::WhiteWins);
+        if mv != expected && !mated {
+            self.notice = Some(format!("Not quite: the solution was {}.", expected));
+            self.finish_puzzle(PuzzleOutcome::Failed);
+            return Ok(());
+        }
+        active.ply += 1;
+        let reply = active.puzzle.solution.get(active.ply).cloned();
+        match reply {
+            Some(reply) if !mated => {
+                active.ply += 1;
+                let solved = active.ply == active.puzzle.solution.len();
+                self.make_move(&reply).await?;
+                self.check_game_over(Outcome::BlackWins).await?;
+                if solved {
+                    self.finish_puzzle(PuzzleOutcome::Solved);
+                }
+            }
+            _ => self.finish_puzzle(PuzzleOutcome::Solved),
+        }
+        Ok(())
+    }
+
+    /// Record the puzzle's outcome and update the streak on the first attempt.
+    fn finish_puzzle(&mut self, outcome: PuzzleOutcome) {
+        let Some(active) = self.puzzle.as_mut() else {
+            return;
+        };
+        active.outcome = outcome;
+        if self.last_scored_puzzle.as_ref() == Some(&active.puzzle.id) {
+            return;
+        }
+        self.last_scored_puzzle = Some(active.puzzle.id.clone());
+        self.puzzle_streak = match outcome {
+            PuzzleOutcome::Solved => self.puzzle_streak + 1,
+            _ => 0,
+        };
+    }
+
+    /// Let the engine play a move, from the opening book if possible.
+    async fn engine_reply(&mut self) -> Result<(), String> {
+        let engine_move = match self.book_move().await? {
+            Some(book_move) => {
+                self.in_book = true;
+                self.analysis = None;
+                book_move
+            }
+            None => {
+                let best = self.engine.best_move().await.map_err(|e| e.to_string())?;
+                self.in_book = false;
+                self.analysis = self.engine.last_search().map(|info| SearchInfo {
+                    depth: info.depth,
+                    score: info.score.negate(),
+                    pv: info.pv.iter().skip(1).cloned().collect(),
+                });
+                best
+            }
+        };
+        if !engine_move.is_empty() {
+            self.make_move(&engine_move).await?;
+            self.check_game_over(Outcome::BlackWins).await?;
+            self.adjudicate_tablebase();
+        }
+        Ok(())
+    }
+
+    /// Pick a legal reply from the opening book for the current position, if any.
+    async fn book_move(&mut self) -> Result<Option<String>, String> {
+        // Book moves use standard castling notation
+        if self.chess960_id.is_some() {
+            return Ok(None);
+        }
+        let (Some(book), Some(fen)) = (&self.book, self.positions.last()) else {
+            return Ok(None);
+        };
+        let Some(mv) = book.pick(fen) else {
+            return Ok(None);
+        };
+        let valid_moves = self.engine.get_valid_moves().await.map_err(|e| e.to_string())?;
+        Ok(valid_moves.contains(&mv).then_some(mv))
+    }
+
+    /// Probe the tablebases for the current position and end the game
+    /// if it is a known win or draw.
+    ///
+    /// Games started from a named position or FEN are endgame drills meant to be
+    /// played out, so they only show the probe.
+    fn adjudicate_tablebase(&mut self) {
+        self.tablebase_probe = match (&self.tablebase, self.positions.last()) {
+            (Some(tb), Some(fen)) => tb.probe(fen),
+            _ => None,
+        };
+        let Some(probe) = self.tablebase_probe else {
+            return;
+        };
+        if self.result.is_none() && self.start_name.is_none() {
+            self.finish(match probe.white_wdl() {
+                Wdl::Win => GameResult::new(Outcome::WhiteWins, "Tablebase win"),
+                Wdl::Loss => GameResult::new(Outcome::BlackWins, "Tablebase win"),
+                Wdl::Draw => GameResult::new(Outcome::Draw, "Tablebase draw"),
+            });
+        }
+    }
+
+    /// Record checkmate or stalemate if the side to move has no legal moves.
+    /// `mover_wins` is the outcome if the side that just moved delivered mate.
+    async fn check_game_over(&mut self, mover_wins: Outcome) -> Result<(), String> {
+        let moves = self.engine.get_valid_moves().await.map_err(|e| e.to_string())?;
+        if !moves.is_empty() {
+            return Ok(());
+        }
+        let checkers = self.get_checkers().await?;
+        self.finish(if !checkers.is_empty() {
+            GameResult::new(mover_wins, "Checkmate")
+        } else {
+            GameResult::new(Outcome::Draw, "Stalemate")
+        });
+        Ok(())
+    }
+
+    /// Whether the current position has occurred at least three times.
+    fn is_threefold_repetition(&self) -> bool {
+        let Some(current) = self.positions.last() else {
+            return false;
+        };
+        let key = positions::repetition_key(current);
+        self.positions
+            .iter()
+            .filter(|fen| positions::repetition_key(fen) == key)
+            .count()
+            >= 3
+    }
+
+    /// Whether fifty moves by each side have passed without a capture or pawn move.
+    fn is_fifty_move_rule(&self) -> bool {
+        self.positions
+            .last()
+            .and_then(|fen| fen.split_whitespace().nth(4))
+            .and_then(|clock| clock.parse::<u32>().ok())
+            .is_some_and(|clock| clock >= 100)
+    }
+
+    /// Ask the engine (playing Black, with White to move) whether it accepts a draw.
+    async fn engine_accepts_draw(&mut self) -> Result<bool, String> {
+        let score = self.engine.evaluate().await.map_err(|e| e.to_string())?;
+        Ok(match score {
+            Score::Cp(cp) => cp >= DRAW_ACCEPT_MARGIN_CP,
+            Score::Mate(n) => n > 0,
+        })
+    }
+}
+
+/// Check that a promotion names a valid piece, and that a pawn reaching the
+/// last rank names one at all.
+fn validate_promotion(mv: &str, valid_moves: &[String]) -> Result<(), String> {
+    match mv.chars().nth(4) {
+        Some(piece) if !"qrbn".contains(piece) => Err(format!(
+            "Invalid promotion piece '{}': expected q, r, b or n",
+            piece
+        )),
+        None if valid_moves.iter().any(|m| m.len() == 5 && m.starts_with(mv)) => Err(format!(
+            "Promotion piece required for {}: append q, r, b or n",
+            mv
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Name of a side for messages.
+fn side_name(white: bool) -> &'static str {
+    if white { "White" } else { "Black" }
+}
+
+/// Seconds since the Unix epoch.
+fn unix_secs(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Number of occurrences of a piece in the placement field of a FEN.
+fn count_piece(fen: &str, piece: char) -> usize {
+    let placement = fen.split_whitespace().next().unwrap_or_default();
+    placement.chars().filter(|&c| c == piece).count()
+}
+
+/// Conventional material value of a piece in pawns (kings and non-pieces count zero).
+fn piece_value(piece: char) -> i32 {
+    match piece.to_ascii_lowercase() {
+        'p' => 1,
+        'n' | 'b' => 3,
+        'r' => 5,
+        'q' => 9,
+        _ => 0,
+    }
 }
@@ -18,6 +18,73 @@ fn is_uci_move(s: &str) -> bool {
     )
 }
 
+/// Engine evaluation, always from the point of view of the side to move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Score {
+    /// Advantage in centipawns.
+    Cp(i32),
+    /// Forced mate in the given number of moves (negative if being mated).
+    Mate(i32),
+}
+
+impl Score {
+    /// The same score from the opponent's point of view.
+    pub fn negate(self) -> Self {
+        match self {
+            Score::Cp(cp) => Score::Cp(-cp),
+            Score::Mate(n) => Score::Mate(-n),
+        }
+    }
+}
+
+/// Search progress reported by the engine in an `info` line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: u32,
+    pub score: Score,
+    /// Principal variation: the line the engine expects, starting with its best move
+    pub pv: Vec<String>,
+}
+
+// Parse an `info depth D ... score cp|mate N ... pv m1 m2 ...` line.
+fn parse_info(line: &str) -> Option<SearchInfo> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next()? != "info" {
+        return None;
+    }
+    let (mut depth, mut score, mut pv) = (None, None, Vec::new());
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => depth = tokens.next()?.parse().ok(),
+            "score" => {
+                let kind = tokens.next()?;
+                let value = tokens.next()?.parse().ok()?;
+                score = match kind {
+                    "cp" => Some(Score::Cp(value)),
+                    "mate" => Some(Score::Mate(value)),
+                    _ => None,
+                };
+            }
+            // The pv is always the last field of the line
+            "pv" => pv = tokens.by_ref().map(str::to_string).collect(),
+            _ => {}
+        }
+    }
+    Some(SearchInfo {
+        depth: depth?,
+        score: score?,
+        pv,
+    })
+}
+
+/// Position details reported by the engine's `d` command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionInfo {
+    pub fen: String,
+    /// Squares of the pieces giving check to the side to move (e.g., ["b5"])
+    pub checkers: Vec<String>,
+}
+
 /// Manages a Stockfish engine subprocess via UCI.
 pub struct EngineService {
     engine_path: String,
@@ -27,39 +94,36 @@ pub struct EngineService {
     moves: Vec<String>,
     /// Cached legal moves for current position
     valid_moves: Vec<String>,
+    /// Deepest search info reported by the last search
+    last_search: Option<SearchInfo>,
+    /// UCI options applied at startup, kept for restarts
+    options: Vec<(String, String)>,
+    /// Custom starting position (FEN); None means the standard start position
+    start_fen: Option<String>,
 }
 
 impl EngineService {
     /// Launch Stockfish and initialize with UCI handshake and starting position.
-    pub async fn start<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        let path_str = path.as_ref().to_string_lossy().into_owned();
-        // Spawn the engine
-        let mut child = Command::new(&path_str)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .spawn()?;
-
-        let writer = child.stdin.take().ok_or("engine stdin unavailable")?;
-        let stdout = child.stdout.take().ok_or("engine stdout unavailable")?;
-        let mut svc = EngineService {
-            engine_path: path_str,
-            child,
-            writer,
-            reader: BufReader::new(stdout),
-            moves: Vec::new(),
-            valid_moves: Vec::new(),
-        };
-
-        // Handshake
-        svc.send("uci\n").await?;
-        svc.wait_for("uciok").await?;
-        svc.send("isready\n").await?;
-        svc.wait_for("readyok").await?;
-        svc.send("position startpos\n").await?;
+    /// `syzygy_path` is passed as `SyzygyPath` during the handshake and on every restart.
+    pub async fn start<P: AsRef<Path>>(
+        path: P,
+        syzygy_path: Option<&str>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let options = syzygy_path
+            .map(|dir| ("SyzygyPath".to_string(), dir.to_string()))
+            .into_iter()
+            .collect();
+        Self::spawn(path, options).await
+    }
 
-        // Prime legal moves cache
-        svc.valid_moves = svc.get_valid_moves().await?;
-        Ok(svc)
+    /// Set a UCI option (e.g., `SyzygyPath`); it is re-applied whenever the engine restarts.
+    pub async fn set_option(&mut self, name: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        self.options.retain(|(n, _)| n != name);
+        self.options.push((name.to_string(), value.to_string()));
+        let cmd = format!("setoption name {} value {}\n", name, value);
+        self.send(&cmd).await?;
+        self.send("isready\n").await?;
+        self.wait_for("readyok").await
     }
 
     /// Quit the engine cleanly.
@@ -69,35 +133,60 @@ impl EngineService {
         Ok(())
     }
 
-    /// Restart a fresh game (stop + start).
+    /// Restart a fresh game from the standard position (stop + start).
     pub async fn new_game(&mut self) -> Result<(), Box<dyn Error>> {
         let path = self.engine_path.clone();
+        let options = self.options.clone();
         let _ = self.stop().await;
-        *self = EngineService::start(path).await?;
+        *self = EngineService::spawn(path, options).await?;
+        Ok(())
+    }
+
+    /// Start the game from a custom position (FEN), or from the standard one if None.
+    /// Clears the move list.
+    pub async fn set_start_position(&mut self, fen: Option<String>) -> Result<(), Box<dyn Error>> {
+        self.start_fen = fen;
+        self.moves.clear();
+        let cmd = self.position_command();
+        self.send(&cmd).await?;
+        self.valid_moves = self.get_valid_moves().await?;
         Ok(())
     }
 
     /// Find best move at fixed depth (16).
     pub async fn best_move(&mut self) -> Result<String, Box<dyn Error>> {
-        self.send("go depth 16\n").await?;
-        let mut line = String::new();
-        loop {
-            self.reader.read_line(&mut line).await?;
-            if let Some(rest) = line.strip_prefix("bestmove ") {
-                return Ok(rest
-                    .split_whitespace()
-                    .next()
-                    .unwrap_or_default()
-                    .to_string());
-            }
-            line.clear();
-        }
+        self.search(16).await
+    }
+
+    /// Find best move at the given depth.
+    pub async fn best_move_at_depth(&mut self, depth: u32) -> Result<String, Box<dyn Error>> {
+        self.search(depth).await
+    }
+
+    /// Evaluate the current position at fixed depth (16).
+    pub async fn evaluate(&mut self) -> Result<Score, Box<dyn Error>> {
+        self.search(16).await?;
+        Ok(self
+            .last_search
+            .as_ref()
+            .map(|info| info.score)
+            .unwrap_or(Score::Cp(0)))
+    }
+
+    /// Moves played since the starting position, in UCI notation.
+    pub fn moves(&self) -> &[String] {
+        &self.moves
+    }
+
+    /// Search info (depth, score, principal variation) from the last search, if any.
+    pub fn last_search(&self) -> Option<&SearchInfo> {
+        self.last_search.as_ref()
     }
 
     /// Apply a UCI move (e.g., "e2e4") and update legal moves.
     pub async fn make_move(&mut self, mv: &str) -> Result<(), Box<dyn Error>> {
         self.moves.push(mv.to_string());
-        let cmd = format!("position startpos moves {}\n", self.moves.join(" "));
+        let cmd = self.position_command();
         self.send(&cmd).await?;
         self.valid_moves = self.get_valid_moves().await?;
         Ok(())
@@ -105,12 +194,22 @@ impl EngineService {
 
     /// Get current position FEN by issuing 'd'.
     pub async fn get_position(&mut self) -> Result<String, Box<dyn Error>> {
+        Ok(self.describe().await?.fen)
+    }
+
+    /// Get the FEN and checking pieces of the current position by issuing 'd'.
+    pub async fn describe(&mut self) -> Result<PositionInfo, Box<dyn Error>> {
         self.send("d\n").await?;
+        let mut fen = String::new();
         let mut line = String::new();
         loop {
             self.reader.read_line(&mut line).await?;
             if let Some(f) = line.strip_prefix("Fen: ") {
-                return Ok(f.trim().to_string());
+                fen = f.trim().to_string();
+            } else if let Some(c) = line.strip_prefix("Checkers:") {
+                // Checkers is the last line of the 'd' output
+                let checkers = c.split_whitespace().map(str::to_string).collect();
+                return Ok(PositionInfo { fen, checkers });
             }
             line.clear();
         }
@@ -139,6 +238,62 @@ impl EngineService {
 
     //–– Internal helpers ––
 
+    /// Spawn the engine, apply the given UCI options and set up the starting position.
+    async fn spawn<P: AsRef<Path>>(
+        path: P,
+        options: Vec<(String, String)>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path_str = path.as_ref().to_string_lossy().into_owned();
+        // Spawn the engine
+        let mut child = Command::new(&path_str)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+
+        let writer = child.stdin.take().ok_or("engine stdin unavailable")?;
+        let stdout = child.stdout.take().ok_or("engine stdout unavailable")?;
+        let mut svc = EngineService {
+            engine_path: path_str,
+            child,
+            writer,
+            reader: BufReader::new(stdout),
+            moves: Vec::new(),
+            valid_moves: Vec::new(),
+            last_search: None,
+            options,
+            start_fen: None,
+        };
+
+        // Handshake
+        svc.send("uci\n").await?;
+        svc.wait_for("uciok").await?;
+        for (name, value) in svc.options.clone() {
+            let cmd = format!("setoption name {} value {}\n", name, value);
+            svc.send(&cmd).await?;
+        }
+        svc.send("isready\n").await?;
+        svc.wait_for("readyok").await?;
+        svc.send("position startpos\n").await?;
+
+        // Prime legal moves cache
+        svc.valid_moves = svc.get_valid_moves().await?;
+        Ok(svc)
+    }
+
+    /// Build the `position` command for the starting position and moves played so far.
+    fn position_command(&self) -> String {
+        let mut cmd = match &self.start_fen {
+            Some(fen) => format!("position fen {}", fen),
+            None => "position startpos".to_string(),
+        };
+        if !self.moves.is_empty() {
+            cmd.push_str(" moves ");
+            cmd.push_str(&self.moves.join(" "));
+        }
+        cmd.push('\n');
+        cmd
+    }
+
     /// Send a command string to Stockfish.
     async fn send(&mut self, cmd: &str) -> Result<(), Box<dyn Error>> {
         self.writer.write_all(cmd.as_bytes()).await?;
@@ -146,6 +301,27 @@ impl EngineService {
         Ok(())
     }
 
+    /// Search to the given depth, returning the best move and recording the search info.
+    async fn search(&mut self, depth: u32) -> Result<String, Box<dyn Error>> {
+        self.send(&format!("go depth {}\n", depth)).await?;
+        self.last_search = None;
+        let mut line = String::new();
+        loop {
+            self.reader.read_line(&mut line).await?;
+            if let Some(rest) = line.strip_prefix("bestmove ") {
+                return Ok(rest
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string());
+            }
+            if let Some(info) = parse_info(&line) {
+                self.last_search = Some(info);
+            }
+            line.clear();
+        }
+    }
+
     /// Read lines until one equals the expected keyword (trimmed).
     async fn wait_for(&mut self, expected: &str) -> Result<(), Box<dyn Error>> {
         let mut line = String::new();
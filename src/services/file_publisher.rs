@@ -0,0 +1,55 @@
+use crate::services::publisher::{CommitContext, CommitSettings, ReadmePublisher};
+use anyhow::Context;
+use async_trait::async_trait;
+use pulldown_cmark::{Options, Parser, html};
+use std::path::PathBuf;
+
+/// Configuration for writing the README to a local file instead of publishing it.
+pub struct FileConfig {
+    /// File the rendered README is written to
+    pub path: PathBuf,
+    /// Only used to log the commit message an actual publisher would use
+    pub commit: CommitSettings,
+}
+
+/// Writes the README to a local file, for developing templates without touching a remote.
+pub struct FilePublisher {
+    config: FileConfig,
+}
+
+impl FilePublisher {
+    /// Create a publisher writing to the configured file.
+    pub fn new(config: FileConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl ReadmePublisher for FilePublisher {
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()> {
+        let path = &self.config.path;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, markdown)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        log::info!(
+            "Wrote README to {} ({})",
+            path.display(),
+            self.config.commit.message(context)
+        );
+        Ok(())
+    }
+}
+
+/// Render README markdown as a standalone HTML page, with the GitHub extensions the board uses.
+pub fn render_html(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let mut body = String::new();
+    html::push_html(&mut body, Parser::new_ext(markdown, options));
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>README preview</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        body
+    )
+}
@@ -0,0 +1,108 @@
+use crate::services::publisher::{
+    CommitContext, CommitSettings, ReadmePublisher, UpdateAttempt, update_with_retry,
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{Value, json};
+
+/// Configuration for publishing through the Gitea API.
+pub struct GiteaConfig {
+    pub token: String,
+    /// API root, e.g. "https://gitea.example.com/api/v1"
+    pub api_url: String,
+    /// Repository in the format "owner/repo"
+    pub owner_repo: String,
+    pub readme_path: String,
+    pub branch: String,
+    pub commit: CommitSettings,
+}
+
+/// Publishes the README through Gitea's contents API, using the file's SHA to detect
+/// concurrent changes.
+pub struct GiteaPublisher {
+    client: Client,
+    config: GiteaConfig,
+}
+
+impl GiteaPublisher {
+    /// Create a new GiteaPublisher with the given config.
+    pub fn new(config: GiteaConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// Helper to send a request for the README, returning the status with the JSON body.
+    async fn send_request(
+        &self,
+        method: Method,
+        body: Option<Value>,
+    ) -> anyhow::Result<(StatusCode, Value)> {
+        let url = format!(
+            "{}/repos/{}/contents/{}",
+            self.config.api_url, self.config.owner_repo, self.config.readme_path
+        );
+        let mut req = self
+            .client
+            .request(method, &url)
+            .header("Authorization", format!("token {}", self.config.token));
+        req = match body {
+            Some(json) => req.json(&json),
+            None => req.query(&[("ref", &self.config.branch)]),
+        };
+        let resp = req.send().await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        let json = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text)
+                .with_context(|| format!("Gitea sent an unreadable response ({})", status))?
+        };
+        Ok((status, json))
+    }
+}
+
+#[async_trait]
+impl ReadmePublisher for GiteaPublisher {
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()> {
+        let message = &self.config.commit.message(context);
+        update_with_retry(|| async move {
+            let (status, current) = self.send_request(Method::GET, None).await?;
+            let sha = match status {
+                StatusCode::NOT_FOUND => None,
+                s if s.is_success() => current["sha"].as_str().map(str::to_string),
+                s => anyhow::bail!("Failed to read README from Gitea ({})", s),
+            };
+            let mut json = json!({
+                "content": BASE64_STANDARD.encode(markdown),
+                "message": message,
+                "branch": self.config.branch
+            });
+            self.config.commit.add_identities(&mut json);
+            // Existing files are updated with their SHA, new ones created
+            let method = match sha {
+                Some(sha) => {
+                    json["sha"] = json!(sha);
+                    Method::PUT
+                }
+                None => Method::POST,
+            };
+            let (status, resp) = self.send_request(method, Some(json)).await?;
+            match status {
+                s if s.is_success() => Ok(UpdateAttempt::Done),
+                StatusCode::CONFLICT => Ok(UpdateAttempt::Conflict),
+                s => anyhow::bail!(
+                    "Failed to update README on Gitea ({}): {}",
+                    s,
+                    resp["message"].as_str().unwrap_or("unknown error")
+                ),
+            }
+        })
+        .await
+    }
+}
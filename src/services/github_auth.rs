@@ -0,0 +1,135 @@
+use crate::utils::dates::parse_utc_timestamp;
+use anyhow::{Context, anyhow};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+/// Installation tokens are refreshed once less than this much lifetime remains.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+/// Lifetime of the app JWT; GitHub rejects anything above ten minutes.
+const JWT_LIFETIME: Duration = Duration::from_secs(9 * 60);
+/// Backdate the JWT to tolerate clock drift between us and GitHub.
+const JWT_BACKDATE: Duration = Duration::from_secs(60);
+
+/// How requests to the GitHub API are authenticated.
+pub enum GithubAuth {
+    /// A personal access token used as-is
+    Token(String),
+    /// A GitHub App installation, minting short-lived tokens on demand
+    App(GithubAppAuth),
+}
+
+impl GithubAuth {
+    /// The bearer token to send with the next API request.
+    pub async fn bearer_token(&self) -> anyhow::Result<String> {
+        match self {
+            GithubAuth::Token(token) => Ok(token.clone()),
+            GithubAuth::App(app) => app.installation_token().await,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Claims {
+    iat: u64,
+    exp: u64,
+    iss: String,
+}
+
+/// Installation token together with the moment it stops working.
+struct CachedToken {
+    token: String,
+    expires_at: SystemTime,
+}
+
+/// Credentials of a GitHub App installed on the README repository.
+pub struct GithubAppAuth {
+    client: Client,
+    app_id: String,
+    key: EncodingKey,
+    installation_id: u64,
+    api_base: String,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl GithubAppAuth {
+    /// Create the app credentials from its id, PEM-encoded RSA private key and installation id.
+    /// `api_base` is the API root, e.g. "https://api.github.com".
+    pub fn new(
+        app_id: String,
+        private_key_pem: &[u8],
+        installation_id: u64,
+        api_base: String,
+    ) -> anyhow::Result<Self> {
+        let key = EncodingKey::from_rsa_pem(private_key_pem)
+            .context("GitHub App private key is not a valid RSA PEM")?;
+        Ok(Self {
+            client: Client::new(),
+            app_id,
+            key,
+            installation_id,
+            api_base,
+            cached: Mutex::new(None),
+        })
+    }
+
+    /// Sign a JWT identifying the app, valid for a few minutes from `now`.
+    pub fn app_jwt(&self, now: SystemTime) -> anyhow::Result<String> {
+        let now = now.duration_since(UNIX_EPOCH)?;
+        let claims = Claims {
+            iat: (now - JWT_BACKDATE).as_secs(),
+            exp: (now + JWT_LIFETIME).as_secs(),
+            iss: self.app_id.clone(),
+        };
+        Ok(jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)?)
+    }
+
+    /// Return the cached installation token, exchanging a fresh JWT for a new one if it is
+    /// missing or about to expire.
+    pub async fn installation_token(&self) -> anyhow::Result<String> {
+        let mut cached = self.cached.lock().await;
+        let now = SystemTime::now();
+        if let Some(token) = cached.as_ref()
+            && token.expires_at > now + REFRESH_MARGIN
+        {
+            return Ok(token.token.clone());
+        }
+        let fresh = self.fetch_installation_token(now).await?;
+        let token = fresh.token.clone();
+        *cached = Some(fresh);
+        Ok(token)
+    }
+
+    // Helper to exchange an app JWT for an installation access token
+    async fn fetch_installation_token(&self, now: SystemTime) -> anyhow::Result<CachedToken> {
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            self.api_base, self.installation_id
+        );
+        let resp = self
+            .client
+            .post(&url)
+            .bearer_auth(self.app_jwt(now)?)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "rust-readme-chess")
+            .send()
+            .await?;
+        let status = resp.status();
+        let json: Value = resp.json().await?;
+        let token = json["token"].as_str().ok_or_else(|| {
+            let message = json["message"].as_str().unwrap_or("no token in response");
+            anyhow!("GitHub App token exchange failed ({}): {}", status, message)
+        })?;
+        let expires_at = json["expires_at"]
+            .as_str()
+            .and_then(parse_utc_timestamp)
+            .ok_or_else(|| anyhow!("GitHub App token response has no valid expires_at"))?;
+        Ok(CachedToken {
+            token: token.to_string(),
+            expires_at,
+        })
+    }
+}
@@ -1,16 +1,101 @@
-use reqwest::{Client, Method};
+use crate::config::GithubTargetConfig;
+use crate::services::github_auth::GithubAuth;
+use crate::services::publisher::{
+    CommitContext, CommitSettings, ReadmePublisher, UpdateAttempt, update_with_retry,
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use reqwest::{Client, Method, StatusCode};
 use serde_json::{Value, json};
 use std::sync::Arc;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine as _;
 
+/// Times the README is fetched while waiting for an update to show up.
+const POLL_ATTEMPTS: usize = 10;
+/// Placeholder in a target's README template that the board is rendered into.
+pub const BOARD_PLACEHOLDER: &str = "{board}";
+
+/// How the README is committed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PublishMethod {
+    /// Git Data API: create a tree and a commit, then move the branch (four calls)
+    #[default]
+    GitData,
+    /// Contents API: a single update guarded by the README's blob SHA, retried on conflict
+    Contents,
+}
+
 /// Configuration for GitHub API operations.
 pub struct GithubConfig {
-    pub token: String,
+    pub auth: GithubAuth,
+    /// Repositories API root, e.g. "https://api.github.com/repos"
     pub api_url: String,
-    pub owner_repo: String,
+    /// READMEs the board is published to
+    pub targets: Vec<GithubTarget>,
+    pub commit: CommitSettings,
+    pub method: PublishMethod,
+}
+
+/// A README kept in sync with the game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GithubTarget {
+    /// Repository in the format "owner/repo"
+    pub repo: String,
     pub readme_path: String,
     pub branch: String,
+    /// Commit message template for this target, instead of the shared one
+    pub template: Option<String>,
+    /// README content around the board, with a `{board}` placeholder; the README is only the
+    /// board if unset
+    pub readme_template: Option<String>,
+}
+
+impl GithubTarget {
+    /// Build a target from its configuration, loading its README template file if it has one.
+    pub fn from_config(config: &GithubTargetConfig) -> anyhow::Result<Self> {
+        let readme_template = match &config.readme_template {
+            Some(path) => {
+                let template = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read README template {}", path))?;
+                if !template.contains(BOARD_PLACEHOLDER) {
+                    anyhow::bail!(
+                        "README template {} has no {} placeholder",
+                        path,
+                        BOARD_PLACEHOLDER
+                    );
+                }
+                Some(template)
+            }
+            None => None,
+        };
+        Ok(Self {
+            repo: config.repo.clone(),
+            readme_path: config.readme_path.clone(),
+            branch: config.branch.clone(),
+            template: config.template.clone(),
+            readme_template,
+        })
+    }
+
+    /// The README content for this target: the board rendered into its template, if any.
+    pub fn readme(&self, board_markdown: &str) -> String {
+        match &self.readme_template {
+            Some(template) => template.replace(BOARD_PLACEHOLDER, board_markdown),
+            None => board_markdown.to_string(),
+        }
+    }
+
+    /// Short description of the target for logs and errors, e.g. "owner/repo:README.md@main".
+    pub fn label(&self) -> String {
+        format!("{}:{}@{}", self.repo, self.readme_path, self.branch)
+    }
+}
+
+/// Outcome of publishing to one target.
+pub struct TargetStatus {
+    pub target: String,
+    pub result: anyhow::Result<()>,
 }
 
 #[derive(Clone)]
@@ -29,79 +114,227 @@ impl GithubService {
         }
     }
 
+    /// Publish the board to every target, one after another, returning each target's outcome.
+    pub async fn publish_all(
+        &self,
+        board_markdown: &str,
+        context: &CommitContext,
+    ) -> Vec<TargetStatus> {
+        let mut statuses = Vec::new();
+        for target in &self.config.targets {
+            let readme = target.readme(board_markdown);
+            let result = match self.config.method {
+                PublishMethod::GitData => self.update_readme(target, &readme, context).await,
+                PublishMethod::Contents => {
+                    self.update_readme_contents(target, &readme, context).await
+                }
+            };
+            match &result {
+                Ok(()) => log::info!("Published README to {}", target.label()),
+                Err(e) => log::error!("Failed to publish README to {}: {}", target.label(), e),
+            }
+            statuses.push(TargetStatus {
+                target: target.label(),
+                result,
+            });
+        }
+        statuses
+    }
+
+    /// Build the commit message for a target, using its own template if it has one.
+    fn commit_message(&self, target: &GithubTarget, context: &CommitContext) -> String {
+        match &target.template {
+            Some(template) => CommitSettings {
+                message_template: template.clone(),
+                ..self.config.commit.clone()
+            }
+            .message(context),
+            None => self.config.commit.message(context),
+        }
+    }
+
     /// Update the README file on GitHub with the new board markdown.
-    pub async fn update_readme(&self, board_markdown: &str) -> anyhow::Result<()> {
-        let latest_commit_sha = self.get_latest_commit_sha().await?;
-        let new_tree_sha = self.create_tree_sha(&latest_commit_sha, board_markdown).await?;
-        let new_commit_sha = self.create_commit_sha(&latest_commit_sha, &new_tree_sha).await?;
-        self.update_ref_with_new_commit(&new_commit_sha).await?;
+    pub async fn update_readme(
+        &self,
+        target: &GithubTarget,
+        board_markdown: &str,
+        context: &CommitContext,
+    ) -> anyhow::Result<()> {
+        let latest_commit_sha = self.get_latest_commit_sha(target).await?;
+        let new_tree_sha = self
+            .create_tree_sha(target, &latest_commit_sha, board_markdown)
+            .await?;
+        let message = self.commit_message(target, context);
+        let new_commit_sha = self
+            .create_commit_sha(target, &latest_commit_sha, &new_tree_sha, &message)
+            .await?;
+        self.update_ref_with_new_commit(target, &new_commit_sha).await?;
         Ok(())
     }
 
     /// Get the latest commit SHA for the branch.
-    async fn get_latest_commit_sha(&self) -> anyhow::Result<String> {
-        let endpoint = format!("git/refs/heads/{}", self.config.branch);
-        let resp = self.handle_request(&endpoint, Method::GET, None).await?;
-        Ok(resp["object"]["sha"].as_str().unwrap().to_string())
+    async fn get_latest_commit_sha(&self, target: &GithubTarget) -> anyhow::Result<String> {
+        let endpoint = format!("git/refs/heads/{}", target.branch);
+        let resp = self.handle_request(target, &endpoint, Method::GET, None).await?;
+        resp["object"]["sha"]
+            .as_str()
+            .map(str::to_string)
+            .context("Branch ref has no commit SHA")
     }
 
     /// Create a new tree SHA with the updated README content.
     async fn create_tree_sha(
         &self,
+        target: &GithubTarget,
         latest_commit_sha: &str,
         new_board_state: &str,
     ) -> anyhow::Result<String> {
         let json = json!({
             "base_tree": latest_commit_sha,
             "tree": [{
-                "path": self.config.readme_path,
+                "path": target.readme_path,
                 "mode": "100644",
                 "type": "blob",
                 "content": new_board_state
             }]
         });
-        let resp = self.handle_request("git/trees", Method::POST, Some(json)).await?;
-        Ok(resp["sha"].as_str().unwrap().to_string())
+        let resp = self
+            .handle_request(target, "git/trees", Method::POST, Some(json))
+            .await?;
+        resp["sha"]
+            .as_str()
+            .map(str::to_string)
+            .context("Created tree has no SHA")
     }
 
     /// Create a new commit SHA for the updated tree.
     async fn create_commit_sha(
         &self,
+        target: &GithubTarget,
         latest_commit_sha: &str,
         new_tree_sha: &str,
+        message: &str,
     ) -> anyhow::Result<String> {
-        let json = json!({
-            "message": "Update README",
+        let mut json = json!({
+            "message": message,
             "parents": [latest_commit_sha],
             "tree": new_tree_sha
         });
-        let resp = self.handle_request("git/commits", Method::POST, Some(json)).await?;
-        Ok(resp["sha"].as_str().unwrap().to_string())
+        self.config.commit.add_identities(&mut json);
+        let resp = self
+            .handle_request(target, "git/commits", Method::POST, Some(json))
+            .await?;
+        resp["sha"]
+            .as_str()
+            .map(str::to_string)
+            .context("Created commit has no SHA")
     }
 
     /// Update the branch ref to point to the new commit.
-    async fn update_ref_with_new_commit(&self, new_commit_sha: &str) -> anyhow::Result<()> {
+    async fn update_ref_with_new_commit(
+        &self,
+        target: &GithubTarget,
+        new_commit_sha: &str,
+    ) -> anyhow::Result<()> {
         let json = json!({ "sha": new_commit_sha });
-        let endpoint = format!("git/refs/heads/{}", self.config.branch);
-        self.handle_request(&endpoint, Method::PATCH, Some(json)).await?;
+        let endpoint = format!("git/refs/heads/{}", target.branch);
+        self.handle_request(target, &endpoint, Method::PATCH, Some(json))
+            .await?;
         Ok(())
     }
 
-    /// Helper to send a request to the GitHub API.
+    /// Update the README through the Contents API, retrying if it changed since it was read.
+    pub async fn update_readme_contents(
+        &self,
+        target: &GithubTarget,
+        board_markdown: &str,
+        context: &CommitContext,
+    ) -> anyhow::Result<()> {
+        let message = &self.commit_message(target, context);
+        let endpoint = &format!("contents/{}", target.readme_path);
+        update_with_retry(|| async move {
+            let mut json = json!({
+                "message": message,
+                "content": BASE64_STANDARD.encode(board_markdown),
+                "branch": target.branch
+            });
+            let read_sha = self.get_readme_blob_sha(target).await?;
+            if let Some(sha) = &read_sha {
+                json["sha"] = json!(sha);
+            }
+            self.config.commit.add_identities(&mut json);
+            let (status, resp) = self
+                .send_request(target, endpoint, Method::PUT, Some(json))
+                .await?;
+            match status {
+                s if s.is_success() => Ok(UpdateAttempt::Done),
+                StatusCode::CONFLICT => Ok(UpdateAttempt::Conflict),
+                // Creating a README someone else just created fails for the missing sha
+                StatusCode::UNPROCESSABLE_ENTITY
+                    if read_sha.is_none() && self.get_readme_blob_sha(target).await?.is_some() =>
+                {
+                    Ok(UpdateAttempt::Conflict)
+                }
+                _ => anyhow::bail!(
+                    "Failed to update README ({}): {}",
+                    status,
+                    resp["message"].as_str().unwrap_or("unknown error")
+                ),
+            }
+        })
+        .await
+    }
+
+    /// Get the blob SHA of the README on the branch, or None if it does not exist yet.
+    async fn get_readme_blob_sha(&self, target: &GithubTarget) -> anyhow::Result<Option<String>> {
+        let endpoint = format!("contents/{}?ref={}", target.readme_path, target.branch);
+        let (status, resp) = self
+            .send_request(target, &endpoint, Method::GET, None)
+            .await?;
+        match status {
+            StatusCode::NOT_FOUND => Ok(None),
+            s if s.is_success() => Ok(resp["sha"].as_str().map(str::to_string)),
+            _ => anyhow::bail!(
+                "Failed to read README ({}): {}",
+                status,
+                resp["message"].as_str().unwrap_or("unknown error")
+            ),
+        }
+    }
+
+    /// Helper to send a request to the GitHub API, failing on an error status.
     async fn handle_request(
         &self,
+        target: &GithubTarget,
         endpoint: &str,
         method: Method,
         body: Option<Value>,
     ) -> anyhow::Result<Value> {
-        let url = format!(
-            "{}/{}/{}/{}",
-            self.config.api_url, self.config.owner_repo, self.config.owner_repo, endpoint
-        );
+        let (status, json) = self.send_request(target, endpoint, method, body).await?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "Request to {} failed ({}): {}",
+                endpoint,
+                status,
+                json["message"].as_str().unwrap_or("unknown error")
+            );
+        }
+        Ok(json)
+    }
+
+    /// Helper to send a request to the GitHub API, returning the status with the JSON body.
+    async fn send_request(
+        &self,
+        target: &GithubTarget,
+        endpoint: &str,
+        method: Method,
+        body: Option<Value>,
+    ) -> anyhow::Result<(StatusCode, Value)> {
+        let url = format!("{}/{}/{}", self.config.api_url, target.repo, endpoint);
         let mut req = self
             .client
             .request(method, &url)
-            .bearer_auth(&self.config.token)
+            .bearer_auth(self.config.auth.bearer_token().await?)
             .header("User-Agent", "rust-readme-chess");
 
         if let Some(json_body) = body {
@@ -109,25 +342,21 @@ impl GithubService {
         }
 
         let resp = req.send().await?;
+        let status = resp.status();
         let text = resp.text().await?;
-        let json: Value = serde_json::from_str(&text)?;
-        Ok(json)
+        let json = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text)?
+        };
+        Ok((status, json))
     }
 
     /// Fetch the current README content from GitHub
-    pub async fn fetch_readme(&self) -> anyhow::Result<String> {
-        let url = format!(
-            "https://api.github.com/repos/{}/contents/{}?ref={}",
-            self.config.owner_repo, self.config.readme_path, self.config.branch
-        );
+    pub async fn fetch_readme(&self, target: &GithubTarget) -> anyhow::Result<String> {
+        let endpoint = format!("contents/{}?ref={}", target.readme_path, target.branch);
         let resp = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.config.token)
-            .header("User-Agent", "rust-readme-chess")
-            .send()
-            .await?
-            .json::<serde_json::Value>()
+            .handle_request(target, &endpoint, Method::GET, None)
             .await?;
         let content_b64 = resp["content"].as_str().unwrap_or("");
         let content = BASE64_STANDARD
@@ -138,14 +367,49 @@ impl GithubService {
     }
 
     /// Poll until the README matches the expected content or timeout
-    pub async fn poll_readme_until_updated(&self, expected: &str, max_attempts: usize) -> bool {
+    pub async fn poll_readme_until_updated(
+        &self,
+        target: &GithubTarget,
+        expected: &str,
+        max_attempts: usize,
+    ) -> bool {
         for _ in 0..max_attempts {
-            if let Ok(current) = self.fetch_readme().await {
-                if current.trim() == expected.trim() {
-                    return true;
-                }
+            if let Ok(current) = self.fetch_readme(target).await
+                && current.trim() == expected.trim()
+            {
+                return true;
             }
         }
         false
     }
 }
+
+#[async_trait]
+impl ReadmePublisher for GithubService {
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()> {
+        let statuses = self.publish_all(markdown, context).await;
+        let failed: Vec<String> = statuses
+            .iter()
+            .filter_map(|s| s.result.as_ref().err().map(|e| format!("{}: {}", s.target, e)))
+            .collect();
+        if !failed.is_empty() {
+            anyhow::bail!(
+                "Failed to publish to {} of {} targets ({})",
+                failed.len(),
+                statuses.len(),
+                failed.join("; ")
+            );
+        }
+        Ok(())
+    }
+
+    async fn wait_until_published(&self, expected: &str) -> bool {
+        let mut published = true;
+        for target in &self.config.targets {
+            published &= self
+                .poll_readme_until_updated(target, &target.readme(expected), POLL_ATTEMPTS)
+                .await;
+        }
+        published
+    }
+}
@@ -0,0 +1,137 @@
+use crate::services::publisher::{
+    CommitContext, CommitSettings, ReadmePublisher, UpdateAttempt, update_with_retry,
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde_json::{Value, json};
+
+/// Configuration for publishing through the GitLab API.
+pub struct GitlabConfig {
+    pub token: String,
+    /// API root, e.g. "https://gitlab.com/api/v4"
+    pub api_url: String,
+    /// Project path, e.g. "group/project"
+    pub project: String,
+    pub readme_path: String,
+    pub branch: String,
+    /// Only the author is used; GitLab records the token's user as committer
+    pub commit: CommitSettings,
+}
+
+/// Publishes the README as a commit through GitLab's repository files and commits API,
+/// using the file's last commit to detect concurrent changes.
+pub struct GitlabPublisher {
+    client: Client,
+    config: GitlabConfig,
+}
+
+impl GitlabPublisher {
+    /// Create a new GitlabPublisher with the given config.
+    pub fn new(config: GitlabConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// URL of the project's API resources.
+    fn project_url(&self) -> String {
+        format!(
+            "{}/projects/{}",
+            self.config.api_url,
+            percent_encode(&self.config.project)
+        )
+    }
+
+    /// The last commit that changed the README on the branch, or None if it does not exist.
+    async fn readme_last_commit(&self) -> anyhow::Result<Option<String>> {
+        let url = format!(
+            "{}/repository/files/{}",
+            self.project_url(),
+            percent_encode(&self.config.readme_path)
+        );
+        let resp = self
+            .client
+            .head(&url)
+            .query(&[("ref", &self.config.branch)])
+            .header("PRIVATE-TOKEN", &self.config.token)
+            .send()
+            .await?;
+        match resp.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            s if s.is_success() => resp
+                .headers()
+                .get("X-Gitlab-Last-Commit-Id")
+                .and_then(|id| id.to_str().ok())
+                .map(|id| Some(id.to_string()))
+                .context("GitLab did not report the README's last commit"),
+            s => anyhow::bail!("Failed to read README from GitLab ({})", s),
+        }
+    }
+}
+
+#[async_trait]
+impl ReadmePublisher for GitlabPublisher {
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()> {
+        let message = &self.config.commit.message(context);
+        update_with_retry(|| async move {
+            // Existing files are updated against the commit they were read at, new ones created
+            let mut action = json!({
+                "action": "create",
+                "file_path": self.config.readme_path,
+                "content": markdown
+            });
+            let read_at = self.readme_last_commit().await?;
+            if let Some(last_commit_id) = &read_at {
+                action["action"] = json!("update");
+                action["last_commit_id"] = json!(last_commit_id);
+            }
+            let mut json = json!({
+                "branch": self.config.branch,
+                "commit_message": message,
+                "actions": [action]
+            });
+            if let Some(author) = &self.config.commit.author {
+                json["author_name"] = json!(author.name);
+                json["author_email"] = json!(author.email);
+            }
+            let resp = self
+                .client
+                .post(format!("{}/repository/commits", self.project_url()))
+                .header("PRIVATE-TOKEN", &self.config.token)
+                .json(&json)
+                .send()
+                .await?;
+            let status = resp.status();
+            if status.is_success() {
+                return Ok(UpdateAttempt::Done);
+            }
+            let body: Value = resp.json().await.unwrap_or_default();
+            // GitLab rejects a stale update or a create of an existing file with a 400, so a
+            // README that moved on since it was read tells a lost race from other errors
+            let rejected = matches!(status, StatusCode::BAD_REQUEST | StatusCode::CONFLICT);
+            if rejected && self.readme_last_commit().await? != read_at {
+                return Ok(UpdateAttempt::Conflict);
+            }
+            anyhow::bail!(
+                "Failed to commit README to GitLab ({}): {}",
+                status,
+                body["message"]
+            )
+        })
+        .await
+    }
+}
+
+/// Percent-encode a path segment, as GitLab expects for project and file paths.
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use crate::services::publisher::{CommitContext, CommitSettings, ReadmePublisher};
+use anyhow::Context;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+/// Configuration for publishing into a local git working tree.
+pub struct LocalGitConfig {
+    /// Working tree the README is committed in
+    pub repo_path: PathBuf,
+    /// Path of the README inside the working tree
+    pub readme_path: String,
+    /// Remote (name or URL) to push to after each commit; commits stay local if unset
+    pub remote: Option<String>,
+    /// Branch on the remote that the checked-out branch is pushed to
+    pub branch: String,
+    pub commit: CommitSettings,
+}
+
+/// Publishes the README by committing it in a local git repository with the `git` CLI,
+/// optionally pushing to a remote such as a self-hosted server or a bare repository.
+pub struct LocalGitPublisher {
+    config: LocalGitConfig,
+    /// Serializes git invocations so concurrent updates do not race on the index
+    lock: Mutex<()>,
+}
+
+impl LocalGitPublisher {
+    /// Create a publisher for the given working tree.
+    pub fn new(config: LocalGitConfig) -> Self {
+        Self {
+            config,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Helper to run git in the working tree, returning its output or an error with stderr.
+    async fn git(&self, args: &[&str]) -> anyhow::Result<String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C").arg(&self.config.repo_path).args(args);
+        if let Some(author) = &self.config.commit.author {
+            cmd.env("GIT_AUTHOR_NAME", &author.name)
+                .env("GIT_AUTHOR_EMAIL", &author.email);
+        }
+        if let Some(committer) = &self.config.commit.committer {
+            cmd.env("GIT_COMMITTER_NAME", &committer.name)
+                .env("GIT_COMMITTER_EMAIL", &committer.email);
+        }
+        let output = cmd.output().await.context("Failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+#[async_trait]
+impl ReadmePublisher for LocalGitPublisher {
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().await;
+        let path = self.config.repo_path.join(&self.config.readme_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, markdown)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.git(&["add", "--", &self.config.readme_path]).await?;
+        // Skip the commit if the board did not change (e.g., a repeated request)
+        let status = self
+            .git(&["status", "--porcelain", "--", &self.config.readme_path])
+            .await?;
+        if !status.trim().is_empty() {
+            let message = self.config.commit.message(context);
+            self.git(&["commit", "--quiet", "-m", &message, "--", &self.config.readme_path])
+                .await?;
+        }
+        if let Some(remote) = &self.config.remote {
+            let refspec = format!("HEAD:refs/heads/{}", self.config.branch);
+            self.git(&["push", "--quiet", remote, &refspec]).await?;
+        }
+        Ok(())
+    }
+}
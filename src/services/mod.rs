@@ -1,3 +1,11 @@
+pub mod archive_service;
 pub mod chess_service;
 pub mod engine_service;
+pub mod file_publisher;
+pub mod gitea_publisher;
+pub mod gitlab_publisher;
+pub mod github_auth;
 pub mod github_service;
+pub mod local_git_publisher;
+pub mod oauth_service;
+pub mod publisher;
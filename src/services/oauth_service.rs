@@ -0,0 +1,132 @@
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Configuration for the GitHub OAuth web flow. The URLs can point at a fake provider in tests.
+pub struct OAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    /// Page the visitor is sent to for authorization (e.g., "https://github.com/login/oauth/authorize")
+    pub authorize_url: String,
+    /// Endpoint exchanging the authorization code for an access token
+    pub token_url: String,
+    /// Endpoint returning the authenticated user
+    pub user_url: String,
+    /// Endpoint returning the user's membership in an organization, with `{org}` as placeholder
+    pub org_membership_url: String,
+    /// Scopes to request (e.g., "read:org" to check organization membership)
+    pub scope: Option<String>,
+    /// Callback URL registered for the OAuth app (e.g., "https://your.domain.com/oauth/callback")
+    pub redirect_url: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct MembershipResponse {
+    state: String,
+}
+
+#[derive(Clone)]
+/// Service identifying visitors through the GitHub OAuth web flow.
+pub struct OAuthService {
+    client: Client,
+    config: Arc<OAuthConfig>,
+}
+
+impl OAuthService {
+    /// Create a new OAuthService with the given config.
+    pub fn new(config: Arc<OAuthConfig>) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// URL to send the visitor to, carrying the anti-forgery `state`.
+    pub fn authorize_url(&self, state: &str) -> String {
+        let mut url = reqwest::Url::parse(&self.config.authorize_url)
+            .unwrap_or_else(|_| panic!("Invalid OAuth authorize URL: {}", self.config.authorize_url));
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url);
+        if let Some(scope) = &self.config.scope {
+            url.query_pairs_mut().append_pair("scope", scope);
+        }
+        url.query_pairs_mut().append_pair("state", state);
+        url.into()
+    }
+
+    /// Exchange the authorization code from the callback for an access token.
+    pub async fn exchange_code(&self, code: &str) -> anyhow::Result<String> {
+        let resp: TokenResponse = self
+            .client
+            .post(&self.config.token_url)
+            .header("Accept", "application/json")
+            .header("User-Agent", "rust-readme-chess")
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+                ("code", code),
+                ("redirect_uri", self.config.redirect_url.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match (resp.access_token, resp.error) {
+            (Some(token), None) => Ok(token),
+            (_, error) => anyhow::bail!(
+                "OAuth token exchange failed: {}",
+                resp.error_description
+                    .or(error)
+                    .unwrap_or_else(|| "no access token".to_string())
+            ),
+        }
+    }
+
+    /// Look up the GitHub login of the user owning the access token.
+    pub async fn fetch_login(&self, access_token: &str) -> anyhow::Result<String> {
+        let user: UserResponse = self
+            .client
+            .get(&self.config.user_url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "rust-readme-chess")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(user.login)
+    }
+
+    /// Whether the user owning the access token is an active member of the organization.
+    pub async fn is_org_member(&self, access_token: &str, org: &str) -> anyhow::Result<bool> {
+        let resp = self
+            .client
+            .get(self.config.org_membership_url.replace("{org}", org))
+            .bearer_auth(access_token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "rust-readme-chess")
+            .send()
+            .await?;
+        // GitHub answers 404 (or 403 without the read:org scope) for non-members
+        if matches!(resp.status().as_u16(), 403 | 404) {
+            return Ok(false);
+        }
+        let membership: MembershipResponse = resp.error_for_status()?.json().await?;
+        Ok(membership.state == "active")
+    }
+}
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::future::Future;
+
+/// Times a version-guarded update is attempted when the README changes underneath it.
+pub const MAX_UPDATE_ATTEMPTS: usize = 3;
+
+/// Default commit message for moves; bracketed parts are dropped when a value is missing.
+pub const DEFAULT_COMMIT_TEMPLATE: &str = "{player} played {san}[, engine replied {engine_san}]";
+
+/// Name and email recorded as a commit's author or committer.
+#[derive(Clone, Debug, Serialize)]
+pub struct CommitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+/// How README commits are written.
+#[derive(Clone, Debug)]
+pub struct CommitSettings {
+    /// Message for moves, with `{player}`, `{san}`, `{engine_san}` and `{fen}` placeholders.
+    /// Text in square brackets is left out when a placeholder inside it has no value.
+    pub message_template: String,
+    /// Author and committer; the destination's default identity is used if unset
+    pub author: Option<CommitIdentity>,
+    pub committer: Option<CommitIdentity>,
+    /// Append the position (FEN) and/or the game so far (PGN) to the commit body
+    pub include_fen: bool,
+    pub include_pgn: bool,
+}
+
+impl Default for CommitSettings {
+    fn default() -> Self {
+        Self {
+            message_template: DEFAULT_COMMIT_TEMPLATE.to_string(),
+            author: None,
+            committer: None,
+            include_fen: false,
+            include_pgn: false,
+        }
+    }
+}
+
+/// What a README update records, for the commit message.
+#[derive(Clone, Debug, Default)]
+pub struct CommitContext {
+    /// Description used when the update is not a move (e.g., "Start a new game")
+    pub summary: String,
+    /// Signed-in visitor who played the move
+    pub player: Option<String>,
+    /// The visitor's move in SAN
+    pub san: Option<String>,
+    /// The reply from the engine (or puzzle solution) in SAN
+    pub engine_san: Option<String>,
+    pub fen: String,
+    pub pgn: String,
+}
+
+impl CommitSettings {
+    /// Build the full commit message (subject and optional body) for an update.
+    pub fn message(&self, context: &CommitContext) -> String {
+        let mut message = match &context.san {
+            Some(san) => fill_template(
+                &self.message_template,
+                &[
+                    ("player", Some(context.player.as_deref().unwrap_or("A visitor"))),
+                    ("san", Some(san.as_str())),
+                    ("engine_san", context.engine_san.as_deref()),
+                    ("fen", Some(context.fen.as_str())),
+                ],
+            ),
+            None => context.summary.clone(),
+        };
+        if self.include_fen && !context.fen.is_empty() {
+            message.push_str(&format!("\n\nFEN: {}", context.fen));
+        }
+        if self.include_pgn && !context.pgn.is_empty() {
+            message.push_str(&format!("\n\n{}", context.pgn.trim_end()));
+        }
+        message
+    }
+
+    /// Set the configured author and committer on a commit request, for APIs that take
+    /// them as `{ "name", "email" }` objects.
+    pub fn add_identities(&self, json: &mut Value) {
+        if let Some(author) = &self.author {
+            json["author"] = json!(author);
+        }
+        if let Some(committer) = &self.committer {
+            json["committer"] = json!(committer);
+        }
+    }
+}
+
+/// Result of one attempt at a version-guarded README update.
+pub enum UpdateAttempt {
+    /// The README was written
+    Done,
+    /// The README changed since it was read, so the write was rejected
+    Conflict,
+}
+
+/// Run a README update guarded by the version (blob SHA or last commit) it was read at,
+/// re-reading and retrying while the write loses a race against another writer.
+pub async fn update_with_retry<F, Fut>(mut attempt: F) -> anyhow::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<UpdateAttempt>>,
+{
+    for _ in 0..MAX_UPDATE_ATTEMPTS {
+        match attempt().await? {
+            UpdateAttempt::Done => return Ok(()),
+            UpdateAttempt::Conflict => log::warn!("README changed during update, retrying"),
+        }
+    }
+    anyhow::bail!(
+        "README kept changing during update, gave up after {} attempts",
+        MAX_UPDATE_ATTEMPTS
+    )
+}
+
+/// Destination the rendered README is published to.
+#[async_trait]
+pub trait ReadmePublisher: Send + Sync {
+    /// Publish the rendered README, committing it with a message built from `context`.
+    async fn publish(&self, markdown: &str, context: &CommitContext) -> anyhow::Result<()>;
+
+    /// Wait until the published README shows `expected`, for destinations that serve it
+    /// with a delay. Returns whether it did.
+    async fn wait_until_published(&self, _expected: &str) -> bool {
+        true
+    }
+}
+
+/// Replace `{name}` placeholders with their values. A `[...]` section is dropped if a
+/// placeholder inside it has no value; brackets without placeholders are kept as-is.
+fn fill_template(template: &str, values: &[(&str, Option<&str>)]) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']').map(|i| open + i) else {
+            break;
+        };
+        out.push_str(&fill_placeholders(&rest[..open], values).0);
+        let section = &rest[open + 1..close];
+        match fill_placeholders(section, values) {
+            (_, Some(false)) => {}
+            (text, Some(true)) => out.push_str(&text),
+            (_, None) => out.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(&fill_placeholders(rest, values).0);
+    out
+}
+
+/// Replace placeholders in plain text. Also reports whether the text had any placeholders
+/// and, if so, whether all of them had values.
+fn fill_placeholders(text: &str, values: &[(&str, Option<&str>)]) -> (String, Option<bool>) {
+    let mut out = text.to_string();
+    let mut complete = None;
+    for (name, value) in values {
+        let placeholder = format!("{{{}}}", name);
+        if out.contains(&placeholder) {
+            complete = Some(complete.unwrap_or(true) && value.is_some());
+            out = out.replace(&placeholder, value.unwrap_or(""));
+        }
+    }
+    (out, complete)
+}
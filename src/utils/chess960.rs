@@ -0,0 +1,65 @@
+use rand::Rng;
+
+/// Number of distinct Chess960 starting positions.
+pub const POSITION_COUNT: u32 = 960;
+
+/// Knight placements over the five squares left after bishops and queen.
+const KNIGHT_PAIRS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Pick a random Chess960 position number.
+pub fn random_id() -> u32 {
+    rand::thread_rng().gen_range(0..POSITION_COUNT)
+}
+
+/// Back rank (e.g., "RNBQKBNR") for a position number, using Scharnagl numbering.
+pub fn back_rank(id: u32) -> Option<String> {
+    if id >= POSITION_COUNT {
+        return None;
+    }
+    let mut rank = [None; 8];
+    let mut n = id as usize;
+    // Light-squared bishop on b/d/f/h, dark-squared bishop on a/c/e/g
+    rank[2 * (n % 4) + 1] = Some('B');
+    n /= 4;
+    rank[2 * (n % 4)] = Some('B');
+    n /= 4;
+    place_on_empty(&mut rank, n % 6, 'Q');
+    n /= 6;
+    // Place the second knight first so the first one's index is unaffected
+    let (first, second) = KNIGHT_PAIRS[n];
+    place_on_empty(&mut rank, second, 'N');
+    place_on_empty(&mut rank, first, 'N');
+    // Remaining three squares: rook, king, rook
+    for piece in ['R', 'K', 'R'] {
+        place_on_empty(&mut rank, 0, piece);
+    }
+    Some(rank.iter().map(|sq| sq.unwrap_or('R')).collect())
+}
+
+/// Starting FEN for a position number, with X-FEN castling rights.
+pub fn start_fen(id: u32) -> Option<String> {
+    let white = back_rank(id)?;
+    Some(format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1",
+        white.to_lowercase(),
+        white
+    ))
+}
+
+/// Put a piece on the `index`-th empty square of the rank.
+fn place_on_empty(rank: &mut [Option<char>; 8], index: usize, piece: char) {
+    if let Some(sq) = rank.iter_mut().filter(|sq| sq.is_none()).nth(index) {
+        *sq = Some(piece);
+    }
+}
@@ -0,0 +1,67 @@
+use shakmaty::san::SanPlus;
+use shakmaty::{CastlingMode, Chess, Position};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Bundled ECO table: one opening per line as `code<TAB>name<TAB>moves in SAN`.
+const ECO_TABLE: &str = include_str!("eco.tsv");
+
+/// A named opening from the ECO table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    /// ECO code (e.g., "B90")
+    pub eco: String,
+    /// Opening name (e.g., "Sicilian Defense: Najdorf Variation")
+    pub name: String,
+    /// Defining move sequence in UCI notation
+    pub moves: Vec<String>,
+}
+
+impl fmt::Display for Opening {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.eco)
+    }
+}
+
+/// All openings in the bundled table.
+pub fn openings() -> &'static [Opening] {
+    static OPENINGS: OnceLock<Vec<Opening>> = OnceLock::new();
+    OPENINGS.get_or_init(|| {
+        ECO_TABLE
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                let opening = parse_line(line);
+                if opening.is_none() {
+                    log::warn!("Skipping malformed ECO entry: {}", line);
+                }
+                opening
+            })
+            .collect()
+    })
+}
+
+/// Find the most specific opening whose moves start the given game (UCI moves).
+pub fn lookup(moves: &[String]) -> Option<&'static Opening> {
+    openings()
+        .iter()
+        .filter(|o| moves.starts_with(&o.moves))
+        .max_by_key(|o| o.moves.len())
+}
+
+/// Parse one table line, converting its SAN moves to UCI.
+fn parse_line(line: &str) -> Option<Opening> {
+    let mut fields = line.split('\t');
+    let eco = fields.next()?.trim().to_string();
+    let name = fields.next()?.trim().to_string();
+    let mut pos = Chess::default();
+    let mut moves = Vec::new();
+    // Skip move numbers such as "1."
+    for token in fields.next()?.split_whitespace().filter(|t| !t.ends_with('.')) {
+        let san = SanPlus::from_ascii(token.as_bytes()).ok()?.san;
+        let m = san.to_move(&pos).ok()?;
+        moves.push(m.to_uci(CastlingMode::Standard).to_string());
+        pos.play_unchecked(m);
+    }
+    Some(Opening { eco, name, moves })
+}
@@ -1 +1,9 @@
+pub mod chess960;
+pub mod dates;
+pub mod eco;
+pub mod polyglot;
+pub mod positions;
+pub mod pgn;
 pub mod printer;
+pub mod puzzles;
+pub mod tablebase;
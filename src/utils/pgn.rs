@@ -0,0 +1,102 @@
+use shakmaty::fen::Fen;
+use shakmaty::san::SanPlus;
+use shakmaty::uci::UciMove;
+use shakmaty::{CastlingMode, Chess, Color, Position};
+
+/// Longest movetext line, as recommended by the PGN standard.
+const MAX_LINE_LEN: usize = 80;
+
+/// Render a game as PGN from its tags, starting position and UCI moves.
+///
+/// `SetUp`/`FEN` tags are added for non-standard starts, and `Variant` for Chess960.
+/// Moves that cannot be replayed end the movetext early.
+pub fn write_pgn(
+    tags: &[(&str, String)],
+    start_fen: Option<&str>,
+    chess960: bool,
+    moves: &[String],
+    result: &str,
+) -> String {
+    let mut pos = start_position(start_fen, chess960);
+    let mut out = String::new();
+    for (name, value) in tags {
+        out.push_str(&tag(name, value));
+    }
+    if chess960 {
+        out.push_str(&tag("Variant", "Chess960"));
+    }
+    if let Some(fen) = start_fen {
+        out.push_str(&tag("SetUp", "1"));
+        out.push_str(&tag("FEN", fen));
+    }
+    out.push_str(&tag("Result", result));
+    out.push('\n');
+
+    let mut tokens = Vec::new();
+    for (i, mv) in moves.iter().enumerate() {
+        let Some(legal) = UciMove::from_ascii(mv.as_bytes())
+            .ok()
+            .and_then(|uci| uci.to_move(&pos).ok())
+        else {
+            log::warn!("Stopping PGN at unplayable move {}", mv);
+            break;
+        };
+        match pos.turn() {
+            Color::White => tokens.push(format!("{}.", pos.fullmoves())),
+            Color::Black if i == 0 => tokens.push(format!("{}...", pos.fullmoves())),
+            Color::Black => {}
+        }
+        tokens.push(SanPlus::from_move_and_play_unchecked(&mut pos, legal).to_string());
+    }
+    tokens.push(result.to_string());
+    out.push_str(&wrap(&tokens));
+    out.push('\n');
+    out
+}
+
+/// Convert UCI moves to SAN, stopping at the first move that cannot be replayed.
+pub fn san_moves(start_fen: Option<&str>, chess960: bool, moves: &[String]) -> Vec<String> {
+    let mut pos = start_position(start_fen, chess960);
+    moves
+        .iter()
+        .map_while(|mv| {
+            let legal = UciMove::from_ascii(mv.as_bytes()).ok()?.to_move(&pos).ok()?;
+            Some(SanPlus::from_move_and_play_unchecked(&mut pos, legal).to_string())
+        })
+        .collect()
+}
+
+/// Position the moves are replayed from: the given FEN, or the standard start.
+fn start_position(start_fen: Option<&str>, chess960: bool) -> Chess {
+    let mode = if chess960 {
+        CastlingMode::Chess960
+    } else {
+        CastlingMode::Standard
+    };
+    start_fen
+        .and_then(|fen| Fen::from_ascii(fen.as_bytes()).ok())
+        .and_then(|fen| fen.into_position(mode).ok())
+        .unwrap_or_default()
+}
+
+fn tag(name: &str, value: &str) -> String {
+    format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Join movetext tokens with spaces, breaking lines before they get too long.
+fn wrap(tokens: &[String]) -> String {
+    let mut out = String::new();
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > MAX_LINE_LEN {
+            out.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            out.push(' ');
+            line_len += 1;
+        }
+        out.push_str(token);
+        line_len += token.len();
+    }
+    out
+}
@@ -0,0 +1,132 @@
+use rand::Rng;
+use shakmaty::fen::Fen;
+use shakmaty::uci::UciMove;
+use shakmaty::zobrist::Zobrist64;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position, Role, Square};
+use std::io;
+use std::path::Path;
+
+/// Size in bytes of one Polyglot book entry.
+const ENTRY_SIZE: usize = 16;
+
+/// A single move suggestion from the book.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookMove {
+    /// Move in UCI notation (castling as king-to-destination, e.g. "e1g1")
+    pub uci: String,
+    pub weight: u16,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+}
+
+/// Reader for Polyglot (`.bin`) opening books.
+pub struct PolyglotBook {
+    /// Entries sorted by key, as stored in the file
+    entries: Vec<Entry>,
+}
+
+impl PolyglotBook {
+    /// Load a Polyglot book from disk.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parse a Polyglot book from its raw bytes.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "book size is not a multiple of 16 bytes",
+            ));
+        }
+        let mut entries: Vec<Entry> = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|chunk| Entry {
+                key: u64::from_be_bytes(chunk[0..8].try_into().unwrap()),
+                mv: u16::from_be_bytes(chunk[8..10].try_into().unwrap()),
+                weight: u16::from_be_bytes(chunk[10..12].try_into().unwrap()),
+            })
+            .collect();
+        // Books are sorted by key, but don't rely on it for lookups
+        entries.sort_by_key(|e| e.key);
+        Ok(Self { entries })
+    }
+
+    /// All legal book moves for the position, most popular first.
+    pub fn moves(&self, fen: &str) -> Vec<BookMove> {
+        let Some(pos) = parse_position(fen) else {
+            return Vec::new();
+        };
+        let key = polyglot_key(&pos);
+        let start = self.entries.partition_point(|e| e.key < key);
+        let mut moves: Vec<BookMove> = self.entries[start..]
+            .iter()
+            .take_while(|e| e.key == key)
+            .filter_map(|e| {
+                let uci = decode_move(e.mv, &pos)?;
+                Some(BookMove {
+                    uci,
+                    weight: e.weight,
+                })
+            })
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        moves
+    }
+
+    /// Pick a book move for the position at random, weighted by the book's weights.
+    pub fn pick(&self, fen: &str) -> Option<String> {
+        let moves = self.moves(fen);
+        let total: u32 = moves.iter().map(|m| u32::from(m.weight)).sum();
+        if total == 0 {
+            return moves.into_iter().next().map(|m| m.uci);
+        }
+        let mut roll = rand::thread_rng().gen_range(0..total);
+        for m in moves {
+            let weight = u32::from(m.weight);
+            if roll < weight {
+                return Some(m.uci);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+fn parse_position(fen: &str) -> Option<Chess> {
+    Fen::from_ascii(fen.as_bytes())
+        .ok()?
+        .into_position(CastlingMode::Standard)
+        .ok()
+}
+
+fn polyglot_key(pos: &Chess) -> u64 {
+    pos.zobrist_hash::<Zobrist64>(EnPassantMode::Legal).0
+}
+
+/// Decode a Polyglot move (castling encoded as king-takes-rook) into standard UCI,
+/// returning None if it is not legal in the position.
+fn decode_move(mv: u16, pos: &Chess) -> Option<String> {
+    let to = Square::new(u32::from(mv & 0x3f));
+    let from = Square::new(u32::from((mv >> 6) & 0x3f));
+    let promotion = match (mv >> 12) & 0x7 {
+        0 => None,
+        1 => Some(Role::Knight),
+        2 => Some(Role::Bishop),
+        3 => Some(Role::Rook),
+        4 => Some(Role::Queen),
+        _ => return None,
+    };
+    let uci = UciMove::Normal {
+        from,
+        to,
+        promotion,
+    };
+    let legal = uci.to_move(pos).ok()?;
+    Some(legal.to_uci(CastlingMode::Standard).to_string())
+}
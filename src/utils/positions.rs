@@ -0,0 +1,77 @@
+use shakmaty::fen::Fen;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position};
+
+/// A named starting position (endgame drill or famous study).
+pub struct NamedPosition {
+    /// Identifier used in `/new?position=<key>`
+    pub key: &'static str,
+    pub name: &'static str,
+    pub fen: &'static str,
+}
+
+/// Catalog of named starting positions.
+pub const CATALOG: &[NamedPosition] = &[
+    NamedPosition {
+        key: "kq-vs-k",
+        name: "Queen checkmate drill",
+        fen: "8/8/8/4k3/8/8/8/3QK3 w - - 0 1",
+    },
+    NamedPosition {
+        key: "kr-vs-k",
+        name: "Rook checkmate drill",
+        fen: "8/8/8/4k3/8/8/8/R3K3 w - - 0 1",
+    },
+    NamedPosition {
+        key: "kbn-vs-k",
+        name: "Bishop and knight checkmate drill",
+        fen: "8/8/8/4k3/8/8/8/2B1KN2 w - - 0 1",
+    },
+    NamedPosition {
+        key: "lucena",
+        name: "Lucena position",
+        fen: "1K1k4/1P6/8/8/8/8/r7/2R5 w - - 0 1",
+    },
+    NamedPosition {
+        key: "reti",
+        name: "Réti endgame study",
+        fen: "7K/8/k1P5/7p/8/8/8/8 w - - 0 1",
+    },
+    NamedPosition {
+        key: "saavedra",
+        name: "Saavedra position",
+        fen: "8/8/1KP5/3r4/8/8/8/k7 w - - 0 1",
+    },
+];
+
+/// Look up a named position by key.
+pub fn find(key: &str) -> Option<&'static NamedPosition> {
+    CATALOG.iter().find(|p| p.key == key)
+}
+
+/// Check that a FEN describes a legal, playable position and return it normalized.
+pub fn validate_fen(fen: &str) -> Result<String, String> {
+    let parsed = Fen::from_ascii(fen.trim().as_bytes())
+        .map_err(|e| format!("Invalid FEN '{}': {}", fen, e))?;
+    let pos: Chess = parsed
+        .into_position(CastlingMode::Standard)
+        .map_err(|e| format!("Illegal position '{}': {}", fen, e))?;
+    if pos.is_game_over() {
+        return Err(format!(
+            "Position '{}' has no legal moves or insufficient material",
+            fen
+        ));
+    }
+    Ok(Fen::from_position(&pos, EnPassantMode::Legal).to_string())
+}
+
+/// Key identifying a position for repetition checks: placement, side to move, castling rights
+/// and en passant square. Castling rights are normalized so the standard letters (`KQkq`) of
+/// a Chess960 start and the Shredder letters (`HAha`) the engine reports compare equal.
+pub fn repetition_key(fen: &str) -> String {
+    let normalized = Fen::from_ascii(fen.as_bytes())
+        .ok()
+        .and_then(|parsed| parsed.into_position::<Chess>(CastlingMode::Chess960).ok())
+        .map(|pos| Fen::from_position(&pos, EnPassantMode::Legal).to_string());
+    let fen = normalized.as_deref().unwrap_or(fen);
+    fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
+}
@@ -1,15 +1,194 @@
+use crate::services::archive_service::{ArchivedGame, LeaderboardEntry, MoveRecord};
+use crate::services::chess_service::{
+    CapturedPieces, ClockState, GameResult, PuzzleOutcome, PuzzleProgress,
+};
+use crate::services::engine_service::{Score, SearchInfo};
+use crate::utils::eco::Opening;
+use crate::utils::dates;
+use crate::utils::tablebase::TablebaseProbe;
 use std::collections::HashSet;
+use std::time::Duration;
 
 /// MarkdownPrinter renders the chess board and controls as Markdown for the README.
 pub struct MarkdownPrinter {
     base_url: String,
     owner_repo: String,
+    /// Web root of the GitHub instance that profiles are linked on
+    web_url: String,
+    /// Page the player's pieces link to; the repository on `web_url` if unset
+    profile_url: Option<String>,
+    result: Option<GameResult>,
+    notice: Option<String>,
+    checkers: Vec<String>,
+    captured: CapturedPieces,
+    analysis: Option<SearchInfo>,
+    hint: Option<String>,
+    hints_used: u32,
+    in_book: bool,
+    opening: Option<Opening>,
+    tablebase: Option<TablebaseProbe>,
+    chess960_id: Option<u32>,
+    start_name: Option<String>,
+    puzzle: Option<PuzzleProgress>,
+    /// Visitors play both sides, so the side to move is selectable
+    human_vs_human: bool,
+    clock: Option<ClockState>,
+    recent_games: Vec<ArchivedGame>,
+    recent_moves: Vec<MoveRecord>,
+    leaderboard: Vec<LeaderboardEntry>,
+    login_enabled: bool,
 }
 
 impl MarkdownPrinter {
     /// Create a new MarkdownPrinter with the given base URL and owner/repo.
     pub fn new(base_url: String, owner_repo: String) -> Self {
-        MarkdownPrinter { base_url, owner_repo }
+        MarkdownPrinter {
+            base_url,
+            owner_repo,
+            web_url: DEFAULT_WEB_URL.to_string(),
+            profile_url: None,
+            result: None,
+            notice: None,
+            checkers: Vec::new(),
+            captured: CapturedPieces::default(),
+            analysis: None,
+            hint: None,
+            hints_used: 0,
+            in_book: false,
+            opening: None,
+            tablebase: None,
+            chess960_id: None,
+            start_name: None,
+            puzzle: None,
+            human_vs_human: false,
+            clock: None,
+            recent_games: Vec::new(),
+            recent_moves: Vec::new(),
+            leaderboard: Vec::new(),
+            login_enabled: false,
+        }
+    }
+
+    /// Show the result of a finished game below the board.
+    pub fn with_result(mut self, result: Option<GameResult>) -> Self {
+        self.result = result;
+        self
+    }
+
+    /// Mark the pieces giving check and the king they attack.
+    pub fn with_checkers(mut self, checkers: Vec<String>) -> Self {
+        self.checkers = checkers;
+        self
+    }
+
+    /// Show the captured pieces and material balance under the board.
+    pub fn with_captured(mut self, captured: CapturedPieces) -> Self {
+        self.captured = captured;
+        self
+    }
+
+    /// Show an evaluation bar and the engine's expected line (score from White's point of view).
+    pub fn with_analysis(mut self, analysis: Option<SearchInfo>) -> Self {
+        self.analysis = analysis;
+        self
+    }
+
+    /// Highlight a suggested move's from/to squares and show how many hints were used.
+    pub fn with_hint(mut self, hint: Option<String>, hints_used: u32) -> Self {
+        self.hint = hint;
+        self.hints_used = hints_used;
+        self
+    }
+
+    /// Note that the engine's last reply came from its opening book.
+    pub fn with_book(mut self, in_book: bool) -> Self {
+        self.in_book = in_book;
+        self
+    }
+
+    /// Show the name and ECO code of the opening above the board.
+    pub fn with_opening(mut self, opening: Option<Opening>) -> Self {
+        self.opening = opening;
+        self
+    }
+
+    /// Show the tablebase value of the position for the side to move
+    /// (e.g., "White to move wins; 23 plies to the next capture or pawn move").
+    pub fn with_tablebase(mut self, probe: Option<TablebaseProbe>) -> Self {
+        self.tablebase = probe;
+        self
+    }
+
+    /// Label the game as Chess960 with its starting position number.
+    pub fn with_chess960(mut self, id: Option<u32>) -> Self {
+        self.chess960_id = id;
+        self
+    }
+
+    /// Name the custom position the game started from (e.g., "Lucena position").
+    pub fn with_start_name(mut self, name: Option<String>) -> Self {
+        self.start_name = name;
+        self
+    }
+
+    /// Show puzzle mode: the puzzle being solved, its outcome and the streak.
+    pub fn with_puzzle(mut self, puzzle: Option<PuzzleProgress>) -> Self {
+        self.puzzle = puzzle;
+        self
+    }
+
+    /// Let visitors move whichever side is to move, instead of only White.
+    pub fn with_human_vs_human(mut self, human_vs_human: bool) -> Self {
+        self.human_vs_human = human_vs_human;
+        self
+    }
+
+    /// Show the remaining time per side on the move clock.
+    pub fn with_clock(mut self, clock: Option<ClockState>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// List the results of recently archived games below the controls.
+    pub fn with_recent_games(mut self, games: Vec<ArchivedGame>) -> Self {
+        self.recent_games = games;
+        self
+    }
+
+    /// Credit the signed-in visitors behind the most recent moves.
+    pub fn with_recent_moves(mut self, moves: Vec<MoveRecord>) -> Self {
+        self.recent_moves = moves;
+        self
+    }
+
+    /// Show the all-time leaderboard of signed-in visitors.
+    pub fn with_leaderboard(mut self, leaderboard: Vec<LeaderboardEntry>) -> Self {
+        self.leaderboard = leaderboard;
+        self
+    }
+
+    /// Link profiles on this web root (e.g., a GitHub Enterprise server) instead of github.com.
+    pub fn with_web_url(mut self, web_url: String) -> Self {
+        self.web_url = web_url;
+        self
+    }
+
+    /// Link the player's pieces to this page (e.g., the README on GitLab or Gitea).
+    pub fn with_profile_url(mut self, profile_url: String) -> Self {
+        self.profile_url = Some(profile_url);
+        self
+    }
+
+    /// Offer a "Sign in" link so visitors can have their moves credited.
+    pub fn with_login(mut self, enabled: bool) -> Self {
+        self.login_enabled = enabled;
+        self
+    }
+
+    /// Show a one-off notice below the board.
+    pub fn with_notice(mut self, notice: Option<String>) -> Self {
+        self.notice = notice;
+        self
     }
 
     /// Render the full Markdown output (header, board, footer).
@@ -20,8 +199,35 @@ impl MarkdownPrinter {
 
         let mut out = String::with_capacity(2_048);
         out.push_str(HEADER);
+        if let Some(id) = self.chess960_id {
+            out.push_str(&format!("**Variant:** Chess960 (position {})\n\n", id));
+        }
+        if self.human_vs_human {
+            out.push_str("**Mode:** Human vs human — visitors take turns for both sides\n\n");
+        }
+        if let Some(puzzle) = &self.puzzle {
+            out.push_str(&format!(
+                "**Puzzle:** {} — White to play and win\n\n",
+                puzzle.id
+            ));
+        }
+        if let Some(name) = &self.start_name {
+            out.push_str(&format!("**Start position:** {}\n\n", name));
+        }
+        if let Some(opening) = &self.opening {
+            out.push_str(&format!("**Opening:** {}\n\n", opening));
+        }
         out.push_str(&self.render_board(fen_str, moves_slice, selected));
+        out.push_str(&self.captured_row());
+        out.push_str(&self.analysis_rows());
+        if let Some(probe) = &self.tablebase {
+            out.push_str(&format!("\n**Tablebase:** {}\n", probe));
+        }
+        out.push_str(&self.status(fen_str));
         out.push_str(&self.footer());
+        out.push_str(&self.recent_moves_row());
+        out.push_str(&self.recent_games_table());
+        out.push_str(&self.leaderboard_table());
         out
     }
 
@@ -29,6 +235,15 @@ impl MarkdownPrinter {
     fn render_board(&self, fen: &str, valid_moves: &[String], selected: &str) -> String {
         let board = parse_fen(fen);
         let valid: HashSet<&str> = valid_moves.iter().map(String::as_str).collect();
+        let selected_piece = piece_at(&board, selected);
+        // Visitors play White, or whichever side is to move between humans
+        let player_white = !self.human_vs_human || side_to_move(fen) == "White";
+        let checked_king = if self.checkers.is_empty() {
+            None
+        } else {
+            let king = if side_to_move(fen) == "White" { 'K' } else { 'k' };
+            find_piece(&board, king)
+        };
 
         let mut s = String::with_capacity(1_024);
         s.push_str(BOARD_HEADER);
@@ -38,42 +253,261 @@ impl MarkdownPrinter {
             for (file_idx, &square) in row.iter().enumerate() {
                 let file = (b'a' + file_idx as u8) as char;
                 let pos = format!("{}{}", file, rank);
-                s.push_str(&format!(
-                    "  {}  |",
-                    self.render_square(square, &pos, selected, &valid)
-                ));
+                let mut cell = self.render_square(
+                    square,
+                    &pos,
+                    selected,
+                    selected_piece,
+                    player_white,
+                    &valid,
+                );
+                if checked_king.as_deref() == Some(pos.as_str()) {
+                    cell = format!("{}{}", CHECKED_KING_MARK, cell);
+                } else if self.checkers.contains(&pos) {
+                    cell = format!("{}{}", CHECKER_MARK, cell);
+                }
+                let hinted = |range| self.hint.as_deref().and_then(|h| h.get(range));
+                if hinted(0..2) == Some(pos.as_str()) || hinted(2..4) == Some(pos.as_str()) {
+                    cell = format!("{}{}", HINT_MARK, cell);
+                }
+                s.push_str(&format!("  {}  |", cell));
             }
             s.push('\n');
         }
         s
     }
 
-    /// Build the footer section with a dynamic New Game link.
-    fn footer(&self) -> String {
+    /// Build the "Captured" row, omitted until the first capture.
+    fn captured_row(&self) -> String {
+        let captured = &self.captured;
+        if captured.by_white.is_empty() && captured.by_black.is_empty() {
+            return String::new();
+        }
+        let side = |pieces: &[char], lead: i32| {
+            let mut s = if pieces.is_empty() {
+                "–".to_string()
+            } else {
+                pieces.iter().map(|&p| format_piece(p)).collect::<Vec<_>>().join(" ")
+            };
+            if lead > 0 {
+                s.push_str(&format!(" (+{})", lead));
+            }
+            s
+        };
         format!(
+            "\n**Captured:** White: {} · Black: {}\n",
+            side(&captured.by_white, captured.balance),
+            side(&captured.by_black, -captured.balance)
+        )
+    }
+
+    /// Build the evaluation bar and expected continuation, if analysis is available
+    /// (or a book marker and the opening's name when the engine replied from its book).
+    fn analysis_rows(&self) -> String {
+        if self.in_book {
+            return match &self.opening {
+                Some(opening) => format!("\n**Evaluation:** 📖 book move: {}\n", opening),
+                None => "\n**Evaluation:** 📖 book move\n".to_string(),
+            };
+        }
+        let Some(info) = &self.analysis else {
+            return String::new();
+        };
+        // Share of the bar owned by White: one cell per pawn of advantage
+        let cells = EVAL_BAR_CELLS as i32;
+        let white_cells = match info.score {
+            Score::Cp(cp) => (cells / 2 + cp / 100).clamp(0, cells),
+            Score::Mate(n) if n > 0 => cells,
+            Score::Mate(_) => 0,
+        } as usize;
+        let bar = format!(
+            "{}{}",
+            "⬜".repeat(white_cells),
+            "⬛".repeat(EVAL_BAR_CELLS - white_cells)
+        );
+        let mut s = format!(
+            "\n**Evaluation:** {} {} (depth {})\n",
+            format_score(info.score),
+            bar,
+            info.depth
+        );
+        if !info.pv.is_empty() {
+            let line: Vec<&str> = info
+                .pv
+                .iter()
+                .take(PV_DISPLAY_PLIES)
+                .map(String::as_str)
+                .collect();
+            s.push_str(&format!("\n**Engine expects:** {}\n", line.join(" ")));
+        }
+        s
+    }
+
+    /// Build the status lines shown under the board (turn or result, notices).
+    fn status(&self, fen: &str) -> String {
+        let mut s = String::new();
+        if let Some(result) = &self.result {
+            s.push_str(&format!(
+                "\n**Result:** {} ({})\n",
+                result.score(),
+                result.reason
+            ));
+        } else if self.checkers.is_empty() {
+            s.push_str(&format!("\n**Status:** {} to move\n", side_to_move(fen)));
+        } else {
+            s.push_str(&format!(
+                "\n**Status:** {} to move — in check\n",
+                side_to_move(fen)
+            ));
+        }
+        if let Some(clock) = &self.clock {
+            let running = |white: bool| if white == clock.white_to_move { "⏳ " } else { "" };
+            s.push_str(&format!(
+                "\n**Clock:** {}White {} · {}Black {} ({} per move)\n",
+                running(true),
+                format_duration(clock.white),
+                running(false),
+                format_duration(clock.black),
+                format_duration(clock.per_move)
+            ));
+        }
+        if let Some(puzzle) = &self.puzzle {
+            match puzzle.outcome {
+                PuzzleOutcome::InProgress => {}
+                PuzzleOutcome::Solved => s.push_str("\n**Puzzle:** ✅ Solved!\n"),
+                PuzzleOutcome::Failed => s.push_str("\n**Puzzle:** ❌ Failed\n"),
+            }
+            s.push_str(&format!("\n**Streak:** {}\n", puzzle.streak));
+        }
+        if self.hints_used > 0 {
+            s.push_str(&format!("\n**Hints used:** {}\n", self.hints_used));
+        }
+        if let Some(notice) = &self.notice {
+            s.push_str(&format!("\n_{}_\n", notice));
+        }
+        s
+    }
+
+    /// Build the footer section with a dynamic New Game link, plus Hint, Resign
+    /// and Draw links while the game is in progress.
+    fn footer(&self) -> String {
+        let mut s = format!(
             "\n[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({}/new)",
             self.base_url
+        );
+        if let Some(puzzle) = &self.puzzle {
+            if puzzle.outcome == PuzzleOutcome::InProgress {
+                s.push_str(&format!(
+                    " [![Hint](https://img.shields.io/badge/Hint-2196F3)]({}/hint)",
+                    self.base_url
+                ));
+            }
+            s.push_str(&self.login_link());
+            return s;
+        }
+        if self.result.is_none() {
+            s.push_str(&format!(
+                " [![Hint](https://img.shields.io/badge/Hint-2196F3)]({0}/hint) [![Resign](https://img.shields.io/badge/Resign-F44336)]({0}/resign) [![Draw](https://img.shields.io/badge/Draw-9E9E9E)]({0}/draw)",
+                self.base_url
+            ));
+        }
+        s.push_str(&self.login_link());
+        s
+    }
+
+    /// Build the "Sign in" link, if visitor login is enabled.
+    fn login_link(&self) -> String {
+        if !self.login_enabled {
+            return String::new();
+        }
+        format!(
+            " [![Sign in](https://img.shields.io/badge/Sign_in-24292F?logo=github)]({}/login)",
+            self.base_url
         )
     }
 
+    /// Build the "Recent moves by" row crediting signed-in visitors.
+    fn recent_moves_row(&self) -> String {
+        if self.recent_moves.is_empty() {
+            return String::new();
+        }
+        let movers: Vec<String> = self
+            .recent_moves
+            .iter()
+            .map(|m| format!("{} ({})", user_link(&self.web_url, &m.login), m.uci))
+            .collect();
+        format!("\n\n**Recent moves by:** {}\n", movers.join(", "))
+    }
+
+    /// Build the all-time leaderboard table.
+    fn leaderboard_table(&self) -> String {
+        if self.leaderboard.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from(
+            "\n\n### Leaderboard\n\n| # | Player | Moves played | Games won vs engine |\n|---|---|---|---|\n",
+        );
+        for (rank, entry) in self.leaderboard.iter().enumerate() {
+            s.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                rank + 1,
+                user_link(&self.web_url, &entry.login),
+                entry.moves,
+                entry.wins
+            ));
+        }
+        s
+    }
+
+    /// Build the table of recently archived games with links to their PGN.
+    fn recent_games_table(&self) -> String {
+        if self.recent_games.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from(
+            "\n\n### Recent games\n\n| Date | White | Black | Result | Moves | PGN |\n|---|---|---|---|---|---|\n",
+        );
+        for game in &self.recent_games {
+            let (year, month, day) = dates::utc_date(game.ended_at);
+            s.push_str(&format!(
+                "| {:04}-{:02}-{:02} | {} | {} | {} ({}) | {} | {} |\n",
+                year,
+                month,
+                day,
+                game.white,
+                game.black,
+                game.result,
+                game.reason,
+                game.plies.div_ceil(2),
+                md_link("PGN", &format!("{}/archive/{}.pgn", self.base_url, game.id))
+            ));
+        }
+        s
+    }
+
     /// Decide how to render a single square (piece, empty, selectable, move target, etc).
     fn render_square(
         &self,
         square: Option<char>,
         pos: &str,
         selected: &str,
+        selected_piece: Option<char>,
+        player_white: bool,
         valid: &HashSet<&str>,
     ) -> String {
         // URL builders using self.base_url
         let select_url = |p: &str| format!("{}/select?square={}", self.base_url, p);
-        let play_url = |mv: &str| format!("{}/play?mv={}", self.base_url, mv);
 
-        let owner_repo = &self.owner_repo;
+        let profile_url = match &self.profile_url {
+            Some(url) => url.clone(),
+            None => get_profile_url(&self.web_url, &self.owner_repo),
+        };
 
         match square {
             Some(piece) => {
                 let piece_md = format_piece(piece);
                 let is_white = piece.is_uppercase();
+                let is_player = is_white == player_white;
 
                 if !selected.is_empty() {
                     // If this is the selected piece -> keep it selected
@@ -81,45 +515,72 @@ impl MarkdownPrinter {
                         return md_link(&piece_md, &select_url(pos));
                     }
                     // If this is a valid move destination -> show move link
-                    let mv = format!("{}{}", selected, pos);
-                    if valid.contains(mv.as_str()) {
-                        return md_link("_", &play_url(&mv));
+                    // (own pieces are targets only for Chess960 king-takes-rook castling)
+                    let own_piece = selected_piece.is_some_and(|p| p.is_uppercase() == is_white);
+                    let label = if own_piece { piece_md.as_str() } else { "_" };
+                    if let Some(link) = self.move_link(selected, pos, valid, label) {
+                        return link;
                     }
-                    // Otherwise, allow re-selecting another white piece that has moves
-                    if is_white && valid.iter().any(|m| m.starts_with(pos)) {
+                    // Otherwise, allow re-selecting another of the player's pieces that has moves
+                    if is_player && valid.iter().any(|m| m.starts_with(pos)) {
                         return md_link(&piece_md, &select_url(pos));
                     }
                     // Else, default render
-                    return if is_white {
-                        md_link(&piece_md, &get_profile_url(owner_repo))
+                    return if is_player {
+                        md_link(&piece_md, &profile_url)
                     } else {
                         piece_md
                     };
                 }
 
-                // No piece selected: allow selecting white pieces that have moves
-                if is_white && valid.iter().any(|m| m.starts_with(pos)) {
+                // No piece selected: allow selecting the player's pieces that have moves
+                if is_player && valid.iter().any(|m| m.starts_with(pos)) {
                     return md_link(&piece_md, &select_url(pos));
                 }
-                // Otherwise, white pieces link to profile, blacks just render
-                if is_white {
-                    md_link(&piece_md, &get_profile_url(owner_repo))
+                // Otherwise, the player's pieces link to profile, the opponent's just render
+                if is_player {
+                    md_link(&piece_md, &profile_url)
                 } else {
                     piece_md
                 }
             }
             None => {
                 // Empty square: if a piece is selected and this is a valid target
-                if !selected.is_empty() {
-                    let mv = format!("{}{}", selected, pos);
-                    if valid.contains(mv.as_str()) {
-                        return md_link("_", &play_url(&mv));
-                    }
+                if !selected.is_empty()
+                    && let Some(link) = self.move_link(selected, pos, valid, "_")
+                {
+                    return link;
                 }
                 " ".into()
             }
         }
     }
+
+    /// Link(s) for moving the selected piece to `pos`, if that is a legal destination.
+    /// Promotions render one link per available piece (e.g., `e7e8q`, `e7e8n`).
+    fn move_link(
+        &self,
+        selected: &str,
+        pos: &str,
+        valid: &HashSet<&str>,
+        label: &str,
+    ) -> Option<String> {
+        let play_url = |mv: &str| format!("{}/play?mv={}", self.base_url, mv);
+        let mv = format!("{}{}", selected, pos);
+        if valid.contains(mv.as_str()) {
+            return Some(md_link(label, &play_url(&mv)));
+        }
+        let choices: Vec<String> = PROMOTION_PIECES
+            .iter()
+            .map(|p| format!("{}{}", mv, p))
+            .filter(|promo| valid.contains(promo.as_str()))
+            .map(|promo| {
+                let label = promo[4..].to_uppercase();
+                md_link(&label, &play_url(&promo))
+            })
+            .collect();
+        (!choices.is_empty()).then(|| choices.join(" "))
+    }
 }
 
 /// Parse FEN into 8×8 board array.
@@ -142,6 +603,49 @@ fn parse_fen(fen: &str) -> Vec<[Option<char>; 8]> {
     rows
 }
 
+/// Name of the side to move according to the FEN.
+fn side_to_move(fen: &str) -> &'static str {
+    match fen.split_whitespace().nth(1) {
+        Some("b") => "Black",
+        _ => "White",
+    }
+}
+
+/// The piece on a square (e.g., "e1"), if any.
+fn piece_at(board: &[[Option<char>; 8]], square: &str) -> Option<char> {
+    let &[file @ b'a'..=b'h', rank @ b'1'..=b'8'] = square.as_bytes() else {
+        return None;
+    };
+    board.get((b'8' - rank) as usize)?[(file - b'a') as usize]
+}
+
+/// Find the square (e.g., "e1") of the first occurrence of a piece on the board.
+fn find_piece(board: &[[Option<char>; 8]], piece: char) -> Option<String> {
+    board.iter().enumerate().find_map(|(row_idx, row)| {
+        let file_idx = row.iter().position(|&sq| sq == Some(piece))?;
+        Some(format!("{}{}", (b'a' + file_idx as u8) as char, 8 - row_idx))
+    })
+}
+
+/// Format a score in pawns (e.g., "+0.35") or as a mate distance (e.g., "#3", "#-2").
+fn format_score(score: Score) -> String {
+    match score {
+        Score::Cp(cp) => format!("{:+.2}", cp as f64 / 100.0),
+        Score::Mate(n) => format!("#{}", n),
+    }
+}
+
+/// Format a clock duration coarsely (e.g., "2d 5h", "5h 12m", "12m").
+fn format_duration(d: Duration) -> String {
+    let minutes = d.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1_440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
 /// Format a piece: bold for white, italic for black.
 fn format_piece(piece: char) -> String {
     if piece.is_uppercase() {
@@ -156,12 +660,32 @@ fn md_link(text: &str, url: &str) -> String {
     format!("[{}]({})", text, url)
 }
 
+/// Link to a visitor's GitHub profile.
+fn user_link(web_url: &str, login: &str) -> String {
+    md_link(&format!("@{}", login), &get_profile_url(web_url, login))
+}
+
 /// Get the GitHub profile URL for the owner/repo.
-fn get_profile_url(owner_repo: &str) -> String {
-    format!("https://github.com/{}", owner_repo)
+fn get_profile_url(web_url: &str, owner_repo: &str) -> String {
+    format!("{}/{}", web_url, owner_repo)
 }
 
 //——— constants ———//
+/// Web root profiles are linked on unless configured otherwise.
+const DEFAULT_WEB_URL: &str = "https://github.com";
+/// Marker placed before the king of the side in check.
+const CHECKED_KING_MARK: &str = "🚨";
+/// Marker placed before each piece giving check.
+const CHECKER_MARK: &str = "⚔️";
+/// Marker placed before the from/to squares of a suggested move.
+const HINT_MARK: &str = "💡";
+/// Number of cells in the evaluation bar.
+const EVAL_BAR_CELLS: usize = 10;
+/// Number of plies of the engine's expected line to display.
+const PV_DISPLAY_PLIES: usize = 6;
+/// Promotion pieces in the order they are offered (UCI suffixes).
+const PROMOTION_PIECES: [char; 4] = ['q', 'r', 'b', 'n'];
+
 const HEADER: &str = r#"# Readme Chess
 
 Welcome to my GitHub profile! Here, you can play a game of chess with me, using my [readme-chess](https://github.com/grim-kalman/rust-readme-chess) application.
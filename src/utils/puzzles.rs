@@ -0,0 +1,101 @@
+use crate::utils::positions;
+use shakmaty::fen::Fen;
+use shakmaty::uci::UciMove;
+use shakmaty::{CastlingMode, Chess, Position};
+use std::io;
+use std::path::Path;
+
+/// A tactic to solve: the player (White) moves first, alternating with the opponent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub id: String,
+    /// Starting position, White to move
+    pub fen: String,
+    /// Solution line in UCI notation, starting with the player's move
+    pub solution: Vec<String>,
+}
+
+/// Puzzles loaded from a local file.
+///
+/// Each line is `id,fen,moves`, with the solution moves separated by spaces
+/// (e.g., `mate-in-2,<fen>,d1d8 e8d8 c1g5`). Blank lines and lines starting
+/// with `#` are ignored.
+pub struct PuzzleSet {
+    puzzles: Vec<Puzzle>,
+}
+
+impl PuzzleSet {
+    /// Load puzzles from disk, skipping malformed or illegal entries.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse puzzles from the file contents.
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        let puzzles: Vec<Puzzle> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| match parse_line(line) {
+                Ok(puzzle) => Some(puzzle),
+                Err(e) => {
+                    log::warn!("Skipping puzzle '{}': {}", line, e);
+                    None
+                }
+            })
+            .collect();
+        if puzzles.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "puzzle file contains no valid puzzles",
+            ));
+        }
+        Ok(Self { puzzles })
+    }
+
+    /// Puzzle for the given day (days since the Unix epoch), cycling through the set.
+    pub fn daily(&self, day: u64) -> &Puzzle {
+        &self.puzzles[(day % self.puzzles.len() as u64) as usize]
+    }
+}
+
+/// Parse and check one `id,fen,moves` line.
+fn parse_line(line: &str) -> Result<Puzzle, String> {
+    let mut fields = line.splitn(3, ',');
+    let (Some(id), Some(fen), Some(moves)) = (fields.next(), fields.next(), fields.next()) else {
+        return Err("expected id,fen,moves".to_string());
+    };
+    let fen = positions::validate_fen(fen)?;
+    if fen.split_whitespace().nth(1) != Some("w") {
+        return Err("puzzles must start with White to move".to_string());
+    }
+    let solution: Vec<String> = moves
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if solution.is_empty() {
+        return Err("empty solution".to_string());
+    }
+    check_solution(&fen, &solution)?;
+    Ok(Puzzle {
+        id: id.trim().to_string(),
+        fen,
+        solution,
+    })
+}
+
+/// Check that every move of the solution is legal in turn.
+fn check_solution(fen: &str, solution: &[String]) -> Result<(), String> {
+    let mut pos: Chess = Fen::from_ascii(fen.as_bytes())
+        .map_err(|e| e.to_string())?
+        .into_position(CastlingMode::Standard)
+        .map_err(|e| e.to_string())?;
+    for mv in solution {
+        let legal = UciMove::from_ascii(mv.as_bytes())
+            .ok()
+            .and_then(|uci| uci.to_move(&pos).ok())
+            .ok_or_else(|| format!("illegal solution move {}", mv))?;
+        pos.play_unchecked(legal);
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use shakmaty::fen::Fen;
+use shakmaty::{CastlingMode, Chess, Color, Position};
+use shakmaty_syzygy::{AmbiguousWdl, Tablebase as Syzygy};
+use std::fmt;
+use std::io;
+
+/// Win/draw/loss value of a position under the fifty-move rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wdl {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Wdl {
+    /// The same value from the opponent's point of view.
+    pub fn flip(self) -> Self {
+        match self {
+            Wdl::Win => Wdl::Loss,
+            Wdl::Draw => Wdl::Draw,
+            Wdl::Loss => Wdl::Win,
+        }
+    }
+}
+
+/// Outcome of probing a position covered by the tablebases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TablebaseProbe {
+    /// Value of the position for the side to move
+    pub wdl: Wdl,
+    /// Plies to the next capture or pawn move that keeps the result (DTZ), if the
+    /// DTZ table is available
+    pub dtz: Option<u32>,
+    /// Whether White is the side to move
+    pub white_to_move: bool,
+}
+
+impl TablebaseProbe {
+    /// Value of the position from White's point of view.
+    pub fn white_wdl(&self) -> Wdl {
+        if self.white_to_move {
+            self.wdl
+        } else {
+            self.wdl.flip()
+        }
+    }
+}
+
+/// Describes the value for the side to move and, if known, the DTZ spelled out as plies to
+/// zeroing, since it is not a distance to mate (e.g., "White to move wins; 13 plies to the
+/// next capture or pawn move").
+impl fmt::Display for TablebaseProbe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self.wdl {
+            Wdl::Draw => return write!(f, "draw"),
+            Wdl::Win => "wins",
+            Wdl::Loss => "loses",
+        };
+        let side = if self.white_to_move { "White" } else { "Black" };
+        write!(f, "{} to move {}", side, value)?;
+        match self.dtz {
+            Some(plies) => write!(f, "; {} plies to the next capture or pawn move", plies),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Local Syzygy tablebases, probed in-crate for win/draw/loss and distance to zeroing.
+pub struct Tablebase {
+    tables: Syzygy<Chess>,
+}
+
+impl Tablebase {
+    /// Add the tables in a directory (or several, separated by ':' as for `SyzygyPath`).
+    pub fn open(path: &str) -> io::Result<Self> {
+        let mut tables = Syzygy::new();
+        for dir in path.split(':').filter(|d| !d.is_empty()) {
+            tables.add_directory(dir)?;
+        }
+        Ok(Self { tables })
+    }
+
+    /// Probe a position, from the side to move's point of view.
+    ///
+    /// Returns `None` for positions the tables do not cover, including positions with
+    /// castling rights, and for wins or losses that the fifty-move rule may still turn
+    /// into a draw.
+    pub fn probe(&self, fen: &str) -> Option<TablebaseProbe> {
+        let pos: Chess = fen
+            .parse::<Fen>()
+            .ok()?
+            .into_position(CastlingMode::Chess960)
+            .ok()?;
+        let wdl = match self.tables.probe_wdl(&pos).ok()? {
+            AmbiguousWdl::Win => Wdl::Win,
+            AmbiguousWdl::Loss => Wdl::Loss,
+            AmbiguousWdl::Draw | AmbiguousWdl::CursedWin | AmbiguousWdl::BlessedLoss => Wdl::Draw,
+            AmbiguousWdl::MaybeWin | AmbiguousWdl::MaybeLoss => return None,
+        };
+        let dtz = match wdl {
+            Wdl::Draw => None,
+            _ => self
+                .tables
+                .probe_dtz(&pos)
+                .ok()
+                .map(|dtz| dtz.ignore_rounding().0.unsigned_abs()),
+        };
+        Some(TablebaseProbe {
+            wdl,
+            dtz,
+            white_to_move: pos.turn() == Color::White,
+        })
+    }
+}
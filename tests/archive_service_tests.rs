@@ -0,0 +1,116 @@
+use rust_readme_chess::services::archive_service::{ArchiveService, ArchivedGame, MoveRecord};
+use std::fs;
+
+// Helper to open an empty archive in a temporary directory
+fn setup_archive(name: &str) -> ArchiveService {
+    let dir = std::env::temp_dir().join(format!("readme-chess-archive-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    ArchiveService::open(dir).unwrap()
+}
+
+// Helper to build game metadata with the given result and players
+fn game(result: &str, players: &[&str]) -> ArchivedGame {
+    ArchivedGame {
+        id: 0,
+        started_at: 1_700_000_000,
+        ended_at: 1_700_000_600,
+        white: "Visitors".to_string(),
+        black: "Stockfish".to_string(),
+        result: result.to_string(),
+        reason: "Checkmate".to_string(),
+        plies: 7,
+        start_fen: None,
+        players: players.iter().map(|p| p.to_string()).collect(),
+    }
+}
+
+/// Test: Stored games get increasing ids and are listed most recent first.
+#[test]
+fn test_archive_store_and_list() {
+    // Arrange
+    let archive = setup_archive("list");
+
+    // Act
+    let first = archive.store(game("1-0", &[]), "1. e4 1-0\n").unwrap();
+    let second = archive.store(game("0-1", &[]), "1. d4 0-1\n").unwrap();
+    let games = archive.list().unwrap();
+
+    // Assert
+    assert_eq!((first.id, second.id), (1, 2));
+    assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(games[0].result, "0-1");
+    assert_eq!(archive.recent(1).unwrap().len(), 1);
+}
+
+/// Test: The PGN of a stored game can be read back, and unknown ids are not found.
+#[test]
+fn test_archive_pgn() {
+    // Arrange
+    let archive = setup_archive("pgn");
+    let stored = archive.store(game("1-0", &[]), "1. e4 1-0\n").unwrap();
+
+    // Act
+    let pgn = archive.pgn(stored.id).unwrap();
+    let missing = archive.pgn(99);
+
+    // Assert
+    assert_eq!(pgn, "1. e4 1-0\n");
+    assert_eq!(missing.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
+
+/// Test: The leaderboard counts attributed moves and wins against the engine per visitor.
+#[test]
+fn test_archive_leaderboard() {
+    // Arrange
+    let archive = setup_archive("leaderboard");
+    for (login, uci) in [("alice", "e2e4"), ("bob", "g1f3"), ("alice", "f1c4")] {
+        let record = MoveRecord {
+            login: login.to_string(),
+            uci: uci.to_string(),
+            at: 1_700_000_000,
+        };
+        archive.record_move(&record).unwrap();
+    }
+    archive.store(game("1-0", &["bob"]), "").unwrap();
+    archive.store(game("0-1", &["alice"]), "").unwrap();
+
+    // Act
+    let board = archive.leaderboard(10).unwrap();
+    let recent = archive.recent_moves(2).unwrap();
+
+    // Assert
+    let rows: Vec<(&str, usize, usize)> = board
+        .iter()
+        .map(|e| (e.login.as_str(), e.moves, e.wins))
+        .collect();
+    assert_eq!(rows, vec![("bob", 1, 1), ("alice", 2, 0)]);
+    assert_eq!(
+        recent.iter().map(|m| m.uci.as_str()).collect::<Vec<_>>(),
+        vec!["f1c4", "g1f3"]
+    );
+}
+
+/// Test: Reopening an archive picks up the next id, the leaderboard and the recent moves.
+#[test]
+fn test_archive_reopen_restores_state() {
+    // Arrange
+    let archive = setup_archive("reopen");
+    let record = MoveRecord {
+        login: "carol".to_string(),
+        uci: "d2d4".to_string(),
+        at: 1_700_000_000,
+    };
+    archive.record_move(&record).unwrap();
+    archive.store(game("1-0", &["carol"]), "").unwrap();
+    let dir = std::env::temp_dir().join("readme-chess-archive-reopen");
+
+    // Act
+    let reopened = ArchiveService::open(dir).unwrap();
+    let next = reopened.store(game("0-1", &[]), "").unwrap();
+
+    // Assert
+    assert_eq!(next.id, 2);
+    let board = reopened.leaderboard(10).unwrap();
+    assert_eq!((board[0].login.as_str(), board[0].moves, board[0].wins), ("carol", 1, 1));
+    assert_eq!(reopened.recent_moves(5).unwrap(), vec![record]);
+}
@@ -0,0 +1,52 @@
+use rust_readme_chess::utils::chess960;
+use rust_readme_chess::utils::printer::MarkdownPrinter;
+use std::collections::HashSet;
+
+/// Test: Position 518 is the standard starting position and ids past 959 are rejected.
+#[test]
+fn test_chess960_standard_position() {
+    // Act
+    let fen = chess960::start_fen(518).unwrap();
+
+    // Assert
+    assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    assert_eq!(chess960::back_rank(960), None);
+}
+
+/// Test: All 960 back ranks are distinct and follow the Chess960 rules.
+#[test]
+fn test_chess960_all_positions_valid() {
+    // Act
+    let ranks: Vec<String> = (0..chess960::POSITION_COUNT)
+        .map(|id| chess960::back_rank(id).unwrap())
+        .collect();
+
+    // Assert
+    assert_eq!(ranks.iter().collect::<HashSet<_>>().len(), 960);
+    for rank in &ranks {
+        let files = |piece| rank.match_indices(piece).map(|(i, _)| i).collect::<Vec<_>>();
+        let (bishops, rooks, king) = (files('B'), files('R'), files('K')[0]);
+        assert_ne!(bishops[0] % 2, bishops[1] % 2, "Bishops on same color: {}", rank);
+        assert!(rooks[0] < king && king < rooks[1], "King not between rooks: {}", rank);
+    }
+}
+
+/// Test: King-takes-rook castling links keep showing the rook.
+#[test]
+fn test_printer_chess960_castling_link() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_chess960(Some(0));
+
+    // Act
+    let md = printer.print(
+        "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1".to_string(),
+        vec!["g1h1".to_string(), "g1f1".to_string()],
+        "g1",
+    );
+
+    // Assert
+    assert!(md.contains("**Variant:** Chess960 (position 0)"));
+    assert!(md.contains("[**R**](http://localhost/play?mv=g1h1)"));
+    assert!(md.contains("[**R**](http://localhost/play?mv=g1f1)"));
+}
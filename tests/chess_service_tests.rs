@@ -1,12 +1,17 @@
 use rust_readme_chess::config::Config;
-use rust_readme_chess::services::chess_service::ChessService;
+use rust_readme_chess::services::chess_service::{
+    ChessService, Opponent, Outcome, PuzzleOutcome, TimeoutAction,
+};
 use rust_readme_chess::services::engine_service::EngineService;
 use rust_readme_chess::utils::printer::MarkdownPrinter;
+use rust_readme_chess::utils::puzzles::PuzzleSet;
+use rust_readme_chess::utils::tablebase::{Tablebase, Wdl};
+use std::time::{Duration, SystemTime};
 
 // Helper to create a ChessService for tests
 async fn setup_chess_service() -> ChessService {
     let config = Config::from_env().unwrap();
-    let engine = EngineService::start(&config.engine_path)
+    let engine = EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
         .await
         .expect("Failed to start engine");
     ChessService::new(engine)
@@ -103,6 +108,20 @@ async fn test_select_square_toggle_hides_move_links() {
     );
 }
 
+/// Test: Selecting something other than a board square returns an error.
+#[tokio::test]
+async fn test_select_invalid_square_fails() {
+    // Setup
+    let mut service = setup_chess_service().await;
+
+    // Action
+    let result = service.select("Release 1.0").await;
+
+    // Assert
+    assert!(result.is_err(), "Invalid square should return an error");
+    assert_eq!(service.get_selected_square(), None);
+}
+
 /// Test: Invalid move returns an error.
 #[tokio::test]
 async fn test_play_invalid_move_fails() {
@@ -115,3 +134,186 @@ async fn test_play_invalid_move_fails() {
     // Assert
     assert!(result.is_err(), "Invalid move should return an error");
 }
+
+/// Test: Resigning records the result and ends the game.
+#[tokio::test]
+async fn test_resign_ends_game() {
+    // Setup
+    let mut service = setup_chess_service().await;
+
+    // Action
+    service.resign().await.unwrap();
+
+    // Assert
+    let result = service.get_result().expect("Game should have a result");
+    assert_eq!(result.outcome, Outcome::BlackWins);
+    assert!(service.get_valid_moves().await.unwrap().is_empty());
+    assert!(service.play("e2e4").await.is_err(), "Moves after resigning should fail");
+}
+
+/// Test: Endgame drills are played out instead of being adjudicated by the tablebase.
+#[tokio::test]
+async fn test_tablebase_drill_is_not_adjudicated() {
+    // Setup
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy");
+    let mut service = setup_chess_service()
+        .await
+        .with_tablebase(Tablebase::open(dir).unwrap());
+    service.new_game_from_named("kq-vs-k").await.unwrap();
+
+    // Action
+    service.play("d1d2").await.unwrap();
+
+    // Assert
+    assert!(service.get_result().is_none(), "The drill should still be in progress");
+    let probe = service.get_tablebase_probe().expect("KQvK should be probed");
+    assert_eq!(probe.white_wdl(), Wdl::Win);
+}
+
+/// Test: Captured pieces start empty and the material is balanced.
+#[tokio::test]
+async fn test_captured_pieces_initially_empty() {
+    // Setup
+    let service = setup_chess_service().await;
+
+    // Action
+    let captured = service.get_captured();
+
+    // Assert
+    assert!(captured.by_white.is_empty());
+    assert!(captured.by_black.is_empty());
+    assert_eq!(captured.balance, 0);
+}
+
+/// Test: Following the puzzle solution plays the opponent's replies and extends the streak.
+#[tokio::test]
+async fn test_puzzle_solved() {
+    // Setup
+    let mut service = setup_chess_service().await;
+    let set = PuzzleSet::parse("back-rank,r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1,d2d8 a8d8 d1d8")
+        .unwrap();
+    service.start_puzzle(set.daily(0).clone()).await.unwrap();
+
+    // Action
+    service.play("d2d8").await.unwrap();
+    let fen = service.get_fen().await.unwrap();
+    service.play("d1d8").await.unwrap();
+
+    // Assert
+    assert!(
+        fen.starts_with("3r2k1/"),
+        "Opponent should reply from the solution, got: {}",
+        fen
+    );
+    let puzzle = service.get_puzzle().expect("Puzzle mode should be active");
+    assert_eq!(puzzle.outcome, PuzzleOutcome::Solved);
+    assert_eq!(puzzle.streak, 1);
+}
+
+/// Test: A move that deviates from the solution fails the puzzle and resets the streak.
+#[tokio::test]
+async fn test_puzzle_wrong_move_fails() {
+    // Setup
+    let mut service = setup_chess_service().await;
+    let set = PuzzleSet::parse("back-rank,r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1,d2d8 a8d8 d1d8")
+        .unwrap();
+    service.start_puzzle(set.daily(0).clone()).await.unwrap();
+
+    // Action
+    service.play("h2h3").await.unwrap();
+
+    // Assert
+    let puzzle = service.get_puzzle().expect("Puzzle mode should be active");
+    assert_eq!(puzzle.outcome, PuzzleOutcome::Failed);
+    assert_eq!(puzzle.streak, 0);
+    assert!(service.get_valid_moves().await.unwrap().is_empty());
+    assert!(
+        service.play("d2d8").await.is_err(),
+        "Moves after failing should be rejected"
+    );
+}
+
+/// Test: Resigning during a puzzle fails it and resets the streak.
+#[tokio::test]
+async fn test_puzzle_resign_fails() {
+    // Setup
+    let mut service = setup_chess_service().await;
+    let set = PuzzleSet::parse(
+        "back-rank,r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1,d2d8 a8d8 d1d8\n\
+         back-rank-2,r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1,d2d8 a8d8 d1d8",
+    )
+    .unwrap();
+    service.start_puzzle(set.daily(0).clone()).await.unwrap();
+    service.play("d2d8").await.unwrap();
+    service.play("d1d8").await.unwrap();
+    let solved_streak = service.get_puzzle().unwrap().streak;
+    service.start_puzzle(set.daily(1).clone()).await.unwrap();
+
+    // Action
+    service.resign().await.unwrap();
+
+    // Assert
+    assert_eq!(solved_streak, 1);
+    let puzzle = service.get_puzzle().expect("Puzzle mode should be active");
+    assert_eq!(puzzle.outcome, PuzzleOutcome::Failed);
+    assert_eq!(puzzle.streak, 0);
+    assert!(service.get_valid_moves().await.unwrap().is_empty());
+    assert!(service.resign().await.is_err(), "The puzzle is already over");
+}
+
+/// Test: Against a human opponent the engine does not reply and Black moves next.
+#[tokio::test]
+async fn test_human_vs_human_alternates_sides() {
+    // Setup
+    let mut service = setup_chess_service().await;
+    service.set_opponent(Opponent::Human);
+    service.new_game().await.unwrap();
+
+    // Action
+    service.play("e2e4").await.unwrap();
+    let after_white = service.get_fen().await.unwrap();
+    service.play("e7e5").await.unwrap();
+    service.resign().await.unwrap();
+
+    // Assert
+    assert!(
+        after_white.split_whitespace().nth(1) == Some("b"),
+        "Black should be to move, got: {}",
+        after_white
+    );
+    let result = service.get_result().expect("Game should have a result");
+    assert_eq!(result.outcome, Outcome::BlackWins, "White was to move and resigned");
+}
+
+/// Test: The side to move loses on time once its move budget runs out.
+#[tokio::test]
+async fn test_clock_forfeits_on_time() {
+    // Setup
+    let mut service = setup_chess_service()
+        .await
+        .with_move_clock(Duration::from_secs(3_600), TimeoutAction::Forfeit);
+    let idle = service
+        .check_clock(SystemTime::now() + Duration::from_secs(7_200))
+        .await
+        .unwrap();
+    service.play("e2e4").await.unwrap();
+    let now = SystemTime::now();
+
+    // Action
+    let before = service.check_clock(now).await.unwrap();
+    let clock = service.get_clock(now).expect("Clock should be running");
+    let after = service
+        .check_clock(now + Duration::from_secs(3_601))
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(!idle, "The clock should not run before the first move");
+    assert!(!before, "Nothing should happen while time remains");
+    assert!(clock.white_to_move);
+    assert_eq!(clock.black, Duration::from_secs(3_600));
+    assert!(after, "Running out of time should end the game");
+    let result = service.get_result().expect("Game should have a result");
+    assert_eq!(result.outcome, Outcome::BlackWins);
+    assert_eq!(result.reason, "White lost on time");
+}
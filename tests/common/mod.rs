@@ -0,0 +1,16 @@
+use actix_web::{App, HttpServer, web};
+
+// Helper to start a local stand-in for a remote API on a free port, returning its base URL.
+// `routes` registers the endpoints, and any state they share, on each worker's app.
+pub fn start_fake_server<F>(routes: F) -> String
+where
+    F: Fn(&mut web::ServiceConfig) + Clone + Send + 'static,
+{
+    let server = HttpServer::new(move || App::new().configure(routes.clone()))
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .expect("Failed to bind fake server");
+    let addr = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+    format!("http://{}", addr)
+}
@@ -0,0 +1,25 @@
+use rust_readme_chess::utils::dates::{parse_utc_timestamp, utc_date};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Test: Unix timestamps are converted to UTC calendar dates.
+#[test]
+fn test_utc_date() {
+    assert_eq!(utc_date(0), (1970, 1, 1));
+    assert_eq!(utc_date(951_782_400), (2000, 2, 29));
+    assert_eq!(utc_date(1_792_368_000), (2026, 10, 19));
+}
+
+/// Test: ISO 8601 UTC timestamps are parsed, and malformed ones rejected.
+#[test]
+fn test_parse_utc_timestamp() {
+    assert_eq!(
+        parse_utc_timestamp("2000-02-29T00:00:00Z"),
+        Some(UNIX_EPOCH + Duration::from_secs(951_782_400))
+    );
+    assert_eq!(
+        parse_utc_timestamp("2016-07-11T22:14:10Z"),
+        Some(UNIX_EPOCH + Duration::from_secs(1_468_275_250))
+    );
+    assert_eq!(parse_utc_timestamp("2016-13-11T22:14:10Z"), None);
+    assert_eq!(parse_utc_timestamp("2016-07-11 22:14:10"), None);
+}
@@ -0,0 +1,60 @@
+use rust_readme_chess::utils::eco;
+use rust_readme_chess::utils::printer::MarkdownPrinter;
+
+// Helper to turn a space-separated UCI line into a move list
+fn moves(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+/// Test: Every line of the bundled ECO table parses into a legal move sequence.
+#[test]
+fn test_eco_table_parses() {
+    // Arrange
+    let table = include_str!("../src/utils/eco.tsv");
+
+    // Act
+    let openings = eco::openings();
+
+    // Assert
+    let lines = table.lines().filter(|l| !l.trim().is_empty()).count();
+    assert_eq!(openings.len(), lines, "Some ECO entries failed to parse");
+}
+
+/// Test: The most specific opening is found, and it persists after leaving theory.
+#[test]
+fn test_eco_lookup_najdorf() {
+    // Arrange
+    let najdorf = moves("e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 a7a6");
+    let mut later = najdorf.clone();
+    later.extend(moves("h2h3 e7e5"));
+
+    // Act
+    let opening = eco::lookup(&najdorf).unwrap();
+    let still = eco::lookup(&later).unwrap();
+
+    // Assert
+    assert_eq!(opening.to_string(), "Sicilian Defense: Najdorf Variation (B90)");
+    assert_eq!(still, opening);
+    assert!(eco::lookup(&[]).is_none());
+}
+
+/// Test: The opening name is rendered above the board, with castling converted to UCI.
+#[test]
+fn test_printer_shows_opening() {
+    // Arrange
+    let ruy = moves("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1");
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_opening(eco::lookup(&ruy).cloned());
+
+    // Act
+    let md = printer.print(
+        "r1bqkb1r/1ppp1ppp/p1n2n2/4p3/B3P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 5 5".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains(
+        "## Chess Board\n**Opening:** Ruy Lopez: Morphy Defense, Normal Variation (C78)\n\n|"
+    ));
+}
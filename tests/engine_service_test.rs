@@ -8,7 +8,7 @@ const AFTER_E4_POSITION: &str = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b
 // Helper function to setup engine for tests
 async fn setup_engine() -> EngineService {
     let config = Config::from_env().unwrap();
-    EngineService::start(&config.engine_path)
+    EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
         .await
         .expect("Failed to start engine")
 }
@@ -19,8 +19,8 @@ fn is_valid_move_format(move_str: &str) -> bool {
         return false;
     }
     let bytes = move_str.as_bytes();
-    let valid_file = |b| b'a' <= b && b <= b'h';
-    let valid_rank = |b| b'1' <= b && b <= b'8';
+    let valid_file = |b| (b'a'..=b'h').contains(&b);
+    let valid_rank = |b| (b'1'..=b'8').contains(&b);
     valid_file(bytes[0]) && valid_rank(bytes[1]) && valid_file(bytes[2]) && valid_rank(bytes[3])
 }
 
@@ -115,3 +115,38 @@ async fn test_new_game_resets_position() {
 
     engine.stop().await.unwrap();
 }
+
+/// Test: Describing a position reports the pieces giving check.
+#[tokio::test]
+async fn test_describe_reports_checkers() {
+    // Arrange
+    let mut engine = setup_engine().await;
+
+    // Act
+    for mv in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+        engine.make_move(mv).await.unwrap();
+    }
+    let info = engine.describe().await.unwrap();
+
+    // Assert
+    assert_eq!(info.checkers, vec!["h4".to_string()]);
+
+    engine.stop().await.unwrap();
+}
+
+/// Test: A search records its depth, score and principal variation.
+#[tokio::test]
+async fn test_best_move_records_search_info() {
+    // Arrange
+    let mut engine = setup_engine().await;
+
+    // Act
+    let best_move = engine.best_move().await.unwrap();
+    let info = engine.last_search().expect("Search info should be recorded").clone();
+
+    // Assert
+    assert_eq!(info.depth, 16);
+    assert_eq!(info.pv.first(), Some(&best_move));
+
+    engine.stop().await.unwrap();
+}
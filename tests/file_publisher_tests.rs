@@ -0,0 +1,43 @@
+use rust_readme_chess::services::file_publisher::{FileConfig, FilePublisher, render_html};
+use rust_readme_chess::services::publisher::{CommitContext, CommitSettings, ReadmePublisher};
+
+/// Test: The README is written to the configured file, creating its directory.
+#[tokio::test]
+async fn test_file_publish_writes_readme() {
+    // Arrange
+    let dir = std::env::temp_dir().join("readme-chess-file-preview");
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("preview").join("README.md");
+    let publisher = FilePublisher::new(FileConfig {
+        path: path.clone(),
+        commit: CommitSettings::default(),
+    });
+    let context = CommitContext {
+        summary: "Start a new game".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board 1\n", &context).await.unwrap();
+    publisher.publish("# Board 2\n", &context).await.unwrap();
+
+    // Assert
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Board 2\n");
+    assert!(publisher.wait_until_published("# Board 2\n").await);
+}
+
+/// Test: The preview renders tables and keeps the inline HTML used by the board.
+#[test]
+fn test_render_html() {
+    // Arrange
+    let markdown = "# Chess\n\n| a | b |\n|---|---|\n| <a href=\"/play\">♙</a> | |\n";
+
+    // Act
+    let html = render_html(markdown);
+
+    // Assert
+    assert!(html.starts_with("<!DOCTYPE html>"), "{}", html);
+    assert!(html.contains("<h1>Chess</h1>"), "{}", html);
+    assert!(html.contains("<table>"), "{}", html);
+    assert!(html.contains("<a href=\"/play\">♙</a>"), "{}", html);
+}
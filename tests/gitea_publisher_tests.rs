@@ -0,0 +1,155 @@
+mod common;
+
+use actix_web::{HttpRequest, HttpResponse, web};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use rust_readme_chess::services::gitea_publisher::{GiteaConfig, GiteaPublisher};
+use rust_readme_chess::services::publisher::{CommitContext, CommitSettings, ReadmePublisher};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+/// README as stored by the fake Gitea repository.
+#[derive(Default)]
+struct FakeRepo {
+    content: Option<String>,
+    /// Bumped on every write, forming the file SHA
+    version: u32,
+    /// Number of upcoming updates that lose a race against another writer
+    conflicts: u32,
+    /// Methods and bodies of successful writes
+    writes: Vec<(String, Value)>,
+}
+
+// Helper to check the token sent with a request
+fn authorized(req: &HttpRequest) -> bool {
+    req.headers().get("Authorization").and_then(|h| h.to_str().ok()) == Some("token gitea-123")
+}
+
+// Helper to start a local stand-in for the contents API of octocat/profile on Gitea,
+// returning its base URL and the shared repository state.
+fn start_fake_gitea(repo: FakeRepo) -> (String, Arc<Mutex<FakeRepo>>) {
+    let repo = Arc::new(Mutex::new(repo));
+    let state = repo.clone();
+    let base = common::start_fake_server(move |cfg| {
+        cfg.app_data(web::Data::new(state.clone())).route(
+            "/api/v1/repos/octocat/profile/contents/README.md",
+            web::route().to(
+                |req: HttpRequest,
+                 repo: web::Data<Arc<Mutex<FakeRepo>>>,
+                 body: Option<web::Json<Value>>| async move {
+                    if !authorized(&req) {
+                        return HttpResponse::Unauthorized()
+                            .json(json!({ "message": "token is required" }));
+                    }
+                    let mut repo = repo.lock().unwrap();
+                    let method = req.method().to_string();
+                    let Some(body) = body else {
+                        return match &repo.content {
+                            Some(_) => HttpResponse::Ok()
+                                .json(json!({ "sha": format!("sha{}", repo.version) })),
+                            None => {
+                                HttpResponse::NotFound().json(json!({ "message": "not found" }))
+                            }
+                        };
+                    };
+                    if repo.conflicts > 0 {
+                        // Someone else committed between our read and write
+                        repo.conflicts -= 1;
+                        repo.version += 1;
+                    }
+                    let current = repo.content.as_ref().map(|_| format!("sha{}", repo.version));
+                    let expected_method = if current.is_some() { "PUT" } else { "POST" };
+                    if body["sha"].as_str() != current.as_deref() || method != expected_method {
+                        return HttpResponse::Conflict()
+                            .json(json!({ "message": "sha does not match" }));
+                    }
+                    let content = BASE64_STANDARD
+                        .decode(body["content"].as_str().unwrap_or(""))
+                        .unwrap_or_default();
+                    repo.content = Some(String::from_utf8_lossy(&content).to_string());
+                    repo.version += 1;
+                    repo.writes.push((method, body.into_inner()));
+                    HttpResponse::Ok().json(json!({}))
+                },
+            ),
+        );
+    });
+    (base, repo)
+}
+
+// Helper to create a publisher for the fake repository
+fn setup_publisher(base: &str) -> GiteaPublisher {
+    GiteaPublisher::new(GiteaConfig {
+        token: "gitea-123".to_string(),
+        api_url: format!("{}/api/v1", base),
+        owner_repo: "octocat/profile".to_string(),
+        readme_path: "README.md".to_string(),
+        branch: "main".to_string(),
+        commit: CommitSettings::default(),
+    })
+}
+
+/// Test: The README is created with POST, then updated with PUT and its SHA.
+#[actix_web::test]
+async fn test_gitea_publish_creates_then_updates() {
+    // Arrange
+    let (base, repo) = start_fake_gitea(FakeRepo::default());
+    let publisher = setup_publisher(&base);
+    let context = CommitContext {
+        summary: "Resign".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board 1", &context).await.unwrap();
+    publisher.publish("# Board 2", &context).await.unwrap();
+
+    // Assert
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.content.as_deref(), Some("# Board 2"));
+    assert_eq!(repo.writes[0].0, "POST");
+    assert_eq!(repo.writes[1].0, "PUT");
+    assert_eq!(repo.writes[1].1["sha"], "sha1");
+    assert_eq!(repo.writes[1].1["message"], "Resign");
+    assert_eq!(repo.writes[1].1["branch"], "main");
+}
+
+/// Test: An update that lost a race is retried with the new SHA.
+#[actix_web::test]
+async fn test_gitea_publish_retries_on_conflict() {
+    // Arrange
+    let (base, repo) = start_fake_gitea(FakeRepo {
+        content: Some("# Old".to_string()),
+        conflicts: 1,
+        ..Default::default()
+    });
+    let publisher = setup_publisher(&base);
+
+    // Act
+    publisher.publish("# New", &CommitContext::default()).await.unwrap();
+
+    // Assert
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.content.as_deref(), Some("# New"));
+    assert_eq!(repo.writes.len(), 1);
+    assert_eq!(repo.writes[0].1["sha"], "sha1");
+}
+
+/// Test: A response that is not JSON is reported instead of read as an empty body.
+#[actix_web::test]
+async fn test_gitea_publish_reports_unreadable_response() {
+    // Arrange
+    let base = common::start_fake_server(|cfg| {
+        cfg.route(
+            "/api/v1/repos/octocat/profile/contents/README.md",
+            web::route().to(|| async { HttpResponse::Ok().body("<html>Bad gateway</html>") }),
+        );
+    });
+    let publisher = setup_publisher(&base);
+
+    // Act
+    let err = publisher.publish("# Board", &CommitContext::default()).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("unreadable response"), "{}", err);
+}
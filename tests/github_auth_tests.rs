@@ -0,0 +1,138 @@
+mod common;
+
+use actix_web::{HttpRequest, HttpResponse, web};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use rust_readme_chess::services::github_auth::{GithubAppAuth, GithubAuth};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PRIVATE_KEY: &[u8] = include_bytes!("fixtures/github_app_key.pem");
+const PUBLIC_KEY: &[u8] = include_bytes!("fixtures/github_app_key.pub.pem");
+const APP_ID: &str = "1234";
+
+// Helper to decode and verify an app JWT with the fixture's public key
+fn decode_jwt(jwt: &str) -> Option<Value> {
+    let key = DecodingKey::from_rsa_pem(PUBLIC_KEY).unwrap();
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&[APP_ID]);
+    jsonwebtoken::decode::<Value>(jwt, &key, &validation)
+        .ok()
+        .map(|data| data.claims)
+}
+
+// Helper to start a local stand-in for GitHub's installation token endpoint, returning its base
+// URL and a counter of issued tokens. Tokens are only issued for JWTs signed by the fixture key
+// for app 1234 and expire at the given timestamp.
+fn start_fake_api(expires_at: &'static str) -> (String, Arc<AtomicUsize>) {
+    let issued = Arc::new(AtomicUsize::new(0));
+    let counter = issued.clone();
+    let base = common::start_fake_server(move |cfg| {
+        let counter = counter.clone();
+        cfg.route(
+            "/app/installations/{id}/access_tokens",
+            web::post().to(move |req: HttpRequest, id: web::Path<u64>| {
+                let counter = counter.clone();
+                async move {
+                    let jwt = req
+                        .headers()
+                        .get("Authorization")
+                        .and_then(|h| h.to_str().ok())
+                        .and_then(|h| h.strip_prefix("Bearer "));
+                    if jwt.and_then(decode_jwt).is_none() || *id != 42 {
+                        return HttpResponse::Unauthorized()
+                            .json(json!({ "message": "A JSON web token could not be decoded" }));
+                    }
+                    let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    HttpResponse::Created().json(json!({
+                        "token": format!("ghs_token{}", n),
+                        "expires_at": expires_at
+                    }))
+                }
+            }),
+        );
+    });
+    (base, issued)
+}
+
+/// Test: The app JWT is RS256-signed, issued by the app and short-lived.
+#[test]
+fn test_app_jwt_claims() {
+    // Arrange
+    let app = GithubAppAuth::new(APP_ID.to_string(), PRIVATE_KEY, 42, String::new()).unwrap();
+    let now = SystemTime::now();
+
+    // Act
+    let claims = decode_jwt(&app.app_jwt(now).unwrap()).expect("JWT should verify");
+
+    // Assert
+    let secs = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let (iat, exp) = (claims["iat"].as_u64().unwrap(), claims["exp"].as_u64().unwrap());
+    assert!(iat <= secs && exp > secs);
+    assert!(exp - iat <= 600, "GitHub rejects JWTs valid for more than ten minutes");
+    assert!(GithubAppAuth::new(APP_ID.to_string(), b"not a key", 42, String::new()).is_err());
+}
+
+/// Test: An installation token is cached until it nears expiry.
+#[actix_web::test]
+async fn test_installation_token_is_cached() {
+    // Arrange
+    let (base, issued) = start_fake_api("2099-01-01T00:00:00Z");
+    let app = GithubAppAuth::new(APP_ID.to_string(), PRIVATE_KEY, 42, base).unwrap();
+    let auth = GithubAuth::App(app);
+
+    // Act
+    let first = auth.bearer_token().await.unwrap();
+    let second = auth.bearer_token().await.unwrap();
+
+    // Assert
+    assert_eq!(first, "ghs_token1");
+    assert_eq!(second, "ghs_token1");
+    assert_eq!(issued.load(Ordering::SeqCst), 1);
+}
+
+/// Test: A token that is about to expire is replaced before use.
+#[actix_web::test]
+async fn test_expiring_installation_token_is_refreshed() {
+    // Arrange
+    let (base, issued) = start_fake_api("2000-01-01T00:00:00Z");
+    let app = GithubAppAuth::new(APP_ID.to_string(), PRIVATE_KEY, 42, base).unwrap();
+
+    // Act
+    let first = app.installation_token().await.unwrap();
+    let second = app.installation_token().await.unwrap();
+
+    // Assert
+    assert_eq!(first, "ghs_token1");
+    assert_eq!(second, "ghs_token2");
+    assert_eq!(issued.load(Ordering::SeqCst), 2);
+}
+
+/// Test: A rejected JWT surfaces GitHub's error message.
+#[actix_web::test]
+async fn test_token_exchange_error() {
+    // Arrange
+    let (base, issued) = start_fake_api("2099-01-01T00:00:00Z");
+    let app = GithubAppAuth::new("9999".to_string(), PRIVATE_KEY, 42, base).unwrap();
+
+    // Act
+    let err = app.installation_token().await.unwrap_err().to_string();
+
+    // Assert
+    assert!(err.contains("could not be decoded"), "{}", err);
+    assert_eq!(issued.load(Ordering::SeqCst), 0);
+}
+
+/// Test: A personal access token is used as the bearer token as is.
+#[actix_web::test]
+async fn test_personal_token() {
+    // Arrange
+    let auth = GithubAuth::Token("ghp_personal".to_string());
+
+    // Act
+    let token = auth.bearer_token().await.unwrap();
+
+    // Assert
+    assert_eq!(token, "ghp_personal");
+}
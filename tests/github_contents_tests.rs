@@ -0,0 +1,275 @@
+mod common;
+
+use actix_web::{HttpResponse, web};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use rust_readme_chess::services::github_auth::GithubAuth;
+use rust_readme_chess::services::github_service::{
+    GithubConfig, GithubService, GithubTarget, PublishMethod,
+};
+use rust_readme_chess::services::publisher::{
+    CommitContext, CommitIdentity, CommitSettings, ReadmePublisher,
+};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+/// README as stored by the fake Contents API.
+#[derive(Default)]
+struct FakeRepo {
+    content: Option<String>,
+    /// Bumped on every write, forming the blob SHA
+    version: u32,
+    /// Number of upcoming updates that lose a race against another writer
+    conflicts: u32,
+    /// Bodies of successful updates
+    commits: Vec<Value>,
+}
+
+// Helper to start a local stand-in for the Contents API of octocat/octocat, returning its
+// base URL and the shared repository state.
+fn start_fake_api(repo: FakeRepo) -> (String, Arc<Mutex<FakeRepo>>) {
+    let repo = Arc::new(Mutex::new(repo));
+    let state = repo.clone();
+    let base = common::start_fake_server(move |cfg| {
+        cfg.app_data(web::Data::new(state.clone()))
+            .route(
+                "/repos/octocat/octocat/contents/README.md",
+                web::get().to(|repo: web::Data<Arc<Mutex<FakeRepo>>>| async move {
+                    let repo = repo.lock().unwrap();
+                    match &repo.content {
+                        Some(content) => HttpResponse::Ok().json(json!({
+                            "sha": format!("sha{}", repo.version),
+                            "content": BASE64_STANDARD.encode(content)
+                        })),
+                        None => HttpResponse::NotFound().json(json!({ "message": "Not Found" })),
+                    }
+                }),
+            )
+            .route(
+                "/repos/octocat/octocat/contents/README.md",
+                web::put().to(
+                    |repo: web::Data<Arc<Mutex<FakeRepo>>>, body: web::Json<Value>| async move {
+                        let mut repo = repo.lock().unwrap();
+                        if repo.conflicts > 0 {
+                            // Someone else committed between our read and write
+                            repo.conflicts -= 1;
+                            repo.version += 1;
+                            repo.content.get_or_insert_with(|| "other board".to_string());
+                        }
+                        let current = repo.content.as_ref().map(|_| format!("sha{}", repo.version));
+                        if current.is_some() && body.get("sha").is_none() {
+                            return HttpResponse::UnprocessableEntity().json(json!({
+                                "message": "Invalid request.\n\n\"sha\" wasn't supplied."
+                            }));
+                        }
+                        if body["sha"].as_str() != current.as_deref() {
+                            return HttpResponse::Conflict()
+                                .json(json!({ "message": "README.md does not match" }));
+                        }
+                        let content = BASE64_STANDARD
+                            .decode(body["content"].as_str().unwrap_or(""))
+                            .unwrap_or_default();
+                        repo.content = Some(String::from_utf8_lossy(&content).to_string());
+                        repo.version += 1;
+                        repo.commits.push(body.into_inner());
+                        HttpResponse::Ok().json(json!({}))
+                    },
+                ),
+            );
+    });
+    (base, repo)
+}
+
+// Helper to describe a README target on the main branch
+fn target(repo: &str, template: Option<&str>) -> GithubTarget {
+    GithubTarget {
+        repo: repo.to_string(),
+        readme_path: "README.md".to_string(),
+        branch: "main".to_string(),
+        template: template.map(str::to_string),
+        readme_template: None,
+    }
+}
+
+// Helper to create a GithubService publishing through the fake Contents API
+fn setup_service(base: &str) -> GithubService {
+    setup_service_with_targets(base, vec![target("octocat/octocat", None)])
+}
+
+// Helper to create a GithubService publishing to the given targets
+fn setup_service_with_targets(base: &str, targets: Vec<GithubTarget>) -> GithubService {
+    GithubService::new(Arc::new(GithubConfig {
+        auth: GithubAuth::Token("token".to_string()),
+        api_url: format!("{}/repos", base),
+        targets,
+        commit: CommitSettings {
+            author: Some(CommitIdentity {
+                name: "Chess Bot".to_string(),
+                email: "bot@example.com".to_string(),
+            }),
+            ..Default::default()
+        },
+        method: PublishMethod::Contents,
+    }))
+}
+
+// Helper to describe a non-move update
+fn context(summary: &str) -> CommitContext {
+    CommitContext {
+        summary: summary.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Test: Publishing creates the README if missing and then updates it with its blob SHA.
+#[actix_web::test]
+async fn test_contents_publish_creates_and_updates() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo::default());
+    let service = setup_service(&base);
+
+    // Act
+    service.publish("first board", &context("Start a new game")).await.unwrap();
+    service.publish("second board", &context("Select e2")).await.unwrap();
+
+    // Assert
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.content.as_deref(), Some("second board"));
+    assert_eq!(repo.commits.len(), 2);
+    assert!(repo.commits[0].get("sha").is_none());
+    assert_eq!(repo.commits[1]["sha"], "sha1");
+    assert_eq!(repo.commits[1]["message"], "Select e2");
+    assert_eq!(repo.commits[1]["branch"], "main");
+    assert_eq!(repo.commits[1]["author"]["name"], "Chess Bot");
+}
+
+/// Test: A conflicting concurrent update is retried with the new blob SHA.
+#[actix_web::test]
+async fn test_contents_publish_retries_on_conflict() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo {
+        content: Some("old board".to_string()),
+        conflicts: 2,
+        ..Default::default()
+    });
+    let service = setup_service(&base);
+
+    // Act
+    service.publish("new board", &context("Resign")).await.unwrap();
+
+    // Assert
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.content.as_deref(), Some("new board"));
+    assert_eq!(repo.commits.len(), 1);
+    assert_eq!(repo.commits[0]["sha"], "sha2");
+}
+
+/// Test: Creating a README that someone else just created is retried as an update.
+#[actix_web::test]
+async fn test_contents_publish_retries_racing_create() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo {
+        conflicts: 1,
+        ..Default::default()
+    });
+    let service = setup_service(&base);
+
+    // Act
+    service.publish("new board", &context("Start a new game")).await.unwrap();
+
+    // Assert
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.content.as_deref(), Some("new board"));
+    assert_eq!(repo.commits.len(), 1);
+    assert_eq!(repo.commits[0]["sha"], "sha1");
+}
+
+/// Test: Publishing gives up when the README keeps changing.
+#[actix_web::test]
+async fn test_contents_publish_gives_up_after_repeated_conflicts() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo {
+        content: Some("old board".to_string()),
+        conflicts: 10,
+        ..Default::default()
+    });
+    let service = setup_service(&base);
+
+    // Act
+    let err = service.publish("new board", &context("Resign")).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("gave up"), "{}", err);
+    assert_eq!(repo.lock().unwrap().content.as_deref(), Some("old board"));
+}
+
+/// Test: Every target is published with its own template, and a failing target is reported
+/// without stopping the others.
+#[actix_web::test]
+async fn test_contents_publish_fans_out_to_targets() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo::default());
+    let service = setup_service_with_targets(
+        &base,
+        vec![
+            target("octocat/missing", None),
+            target("octocat/octocat", Some("Mirror: {san}")),
+        ],
+    );
+    let first_move = CommitContext {
+        san: Some("e4".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    let statuses = service.publish_all("board", &first_move).await;
+
+    // Assert
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[0].target, "octocat/missing:README.md@main");
+    assert!(statuses[0].result.is_err());
+    assert!(statuses[1].result.is_ok());
+    assert_eq!(repo.lock().unwrap().content.as_deref(), Some("board"));
+    assert_eq!(repo.lock().unwrap().commits[0]["message"], "Mirror: e4");
+}
+
+/// Test: A target with a README template keeps its own content around the board.
+#[actix_web::test]
+async fn test_contents_publish_renders_readme_template() {
+    // Arrange
+    let (base, repo) = start_fake_api(FakeRepo::default());
+    let project = GithubTarget {
+        readme_template: Some("# My project\n\n{board}\n\n## License\n".to_string()),
+        ..target("octocat/octocat", None)
+    };
+    let service = setup_service_with_targets(&base, vec![project]);
+
+    // Act
+    service.publish("board", &context("Start a new game")).await.unwrap();
+    let published = service.wait_until_published("board").await;
+
+    // Assert
+    assert_eq!(
+        repo.lock().unwrap().content.as_deref(),
+        Some("# My project\n\nboard\n\n## License\n")
+    );
+    assert!(published);
+}
+
+/// Test: Publishing names the targets that failed.
+#[actix_web::test]
+async fn test_contents_publish_reports_failed_targets() {
+    // Arrange
+    let (base, _repo) = start_fake_api(FakeRepo::default());
+    let service = setup_service_with_targets(
+        &base,
+        vec![target("octocat/missing", None), target("octocat/octocat", None)],
+    );
+
+    // Act
+    let err = service.publish("board", &context("Resign")).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("1 of 2 targets"), "{}", err);
+    assert!(err.to_string().contains("octocat/missing"), "{}", err);
+}
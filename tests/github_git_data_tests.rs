@@ -0,0 +1,130 @@
+mod common;
+
+use actix_web::{HttpResponse, web};
+use rust_readme_chess::services::github_auth::GithubAuth;
+use rust_readme_chess::services::github_service::{
+    GithubConfig, GithubService, GithubTarget, PublishMethod,
+};
+use rust_readme_chess::services::publisher::{CommitContext, CommitSettings};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+/// Branch and objects as stored by the fake Git Data API.
+#[derive(Default)]
+struct FakeRepo {
+    /// Commit the branch points to
+    head: String,
+    /// Bodies of created trees and commits
+    trees: Vec<Value>,
+    commits: Vec<Value>,
+}
+
+// Helper to start a local stand-in for the Git Data API of octocat/octocat, returning its
+// base URL and the shared repository state. Other repositories get an empty 404.
+fn start_fake_api() -> (String, Arc<Mutex<FakeRepo>>) {
+    let repo = Arc::new(Mutex::new(FakeRepo {
+        head: "commit0".to_string(),
+        ..Default::default()
+    }));
+    let state = repo.clone();
+    let base = common::start_fake_server(move |cfg| {
+        cfg.app_data(web::Data::new(state.clone()))
+            .route(
+                "/repos/octocat/octocat/git/refs/heads/main",
+                web::get().to(|repo: web::Data<Arc<Mutex<FakeRepo>>>| async move {
+                    let head = repo.lock().unwrap().head.clone();
+                    HttpResponse::Ok().json(json!({ "object": { "sha": head } }))
+                }),
+            )
+            .route(
+                "/repos/octocat/octocat/git/trees",
+                web::post().to(
+                    |repo: web::Data<Arc<Mutex<FakeRepo>>>, body: web::Json<Value>| async move {
+                        let mut repo = repo.lock().unwrap();
+                        repo.trees.push(body.into_inner());
+                        HttpResponse::Created().json(json!({ "sha": "tree1" }))
+                    },
+                ),
+            )
+            .route(
+                "/repos/octocat/octocat/git/commits",
+                web::post().to(
+                    |repo: web::Data<Arc<Mutex<FakeRepo>>>, body: web::Json<Value>| async move {
+                        let mut repo = repo.lock().unwrap();
+                        repo.commits.push(body.into_inner());
+                        HttpResponse::Created().json(json!({ "sha": "commit1" }))
+                    },
+                ),
+            )
+            .route(
+                "/repos/octocat/octocat/git/refs/heads/main",
+                web::patch().to(
+                    |repo: web::Data<Arc<Mutex<FakeRepo>>>, body: web::Json<Value>| async move {
+                        let mut repo = repo.lock().unwrap();
+                        repo.head = body["sha"].as_str().unwrap_or_default().to_string();
+                        HttpResponse::Ok().json(json!({}))
+                    },
+                ),
+            )
+            .route(
+                "/repos/octocat/private/git/refs/heads/main",
+                web::get().to(|| async {
+                    HttpResponse::Forbidden()
+                        .json(json!({ "message": "Resource not accessible by integration" }))
+                }),
+            );
+    });
+    (base, repo)
+}
+
+// Helper to describe a README target on the main branch
+fn target(repo: &str) -> GithubTarget {
+    GithubTarget {
+        repo: repo.to_string(),
+        readme_path: "README.md".to_string(),
+        branch: "main".to_string(),
+        template: None,
+        readme_template: None,
+    }
+}
+
+// Helper to create a GithubService publishing to the given targets through the Git Data API
+fn setup_service(base: &str, targets: Vec<GithubTarget>) -> GithubService {
+    GithubService::new(Arc::new(GithubConfig {
+        auth: GithubAuth::Token("token".to_string()),
+        api_url: format!("{}/repos", base),
+        targets,
+        commit: CommitSettings::default(),
+        method: PublishMethod::GitData,
+    }))
+}
+
+/// Test: Targets answering with an error are reported per target while the others are
+/// still updated.
+#[actix_web::test]
+async fn test_git_data_publish_reports_failing_target() {
+    // Arrange
+    let (base, repo) = start_fake_api();
+    let context = CommitContext {
+        summary: "Start a new game".to_string(),
+        ..Default::default()
+    };
+    let missing = setup_service(&base, vec![target("octocat/missing"), target("octocat/octocat")]);
+    let forbidden = setup_service(&base, vec![target("octocat/private")]);
+
+    // Act
+    let statuses = missing.publish_all("board", &context).await;
+    let denied = forbidden.publish_all("board", &context).await;
+
+    // Assert
+    assert_eq!(statuses.len(), 2);
+    let err = statuses[0].result.as_ref().unwrap_err();
+    assert!(err.to_string().contains("404"), "{}", err);
+    assert!(statuses[1].result.is_ok());
+    let repo = repo.lock().unwrap();
+    assert_eq!(repo.head, "commit1");
+    assert_eq!(repo.trees[0]["tree"][0]["content"], "board");
+    assert_eq!(repo.commits[0]["parents"][0], "commit0");
+    let err = denied[0].result.as_ref().unwrap_err();
+    assert!(err.to_string().contains("not accessible"), "{}", err);
+}
@@ -1,6 +1,10 @@
-use rust_readme_chess::config::Config;
+use rust_readme_chess::config::{Config, GithubTargetConfig};
 use rust_readme_chess::services::engine_service::EngineService;
-use rust_readme_chess::services::github_service::{GithubConfig, GithubService};
+use rust_readme_chess::services::github_auth::GithubAuth;
+use rust_readme_chess::services::github_service::{
+    GithubConfig, GithubService, GithubTarget, PublishMethod,
+};
+use rust_readme_chess::services::publisher::{CommitContext, CommitSettings};
 use rust_readme_chess::utils::printer::MarkdownPrinter;
 use std::sync::Arc;
 
@@ -9,24 +13,31 @@ use std::sync::Arc;
 async fn test_update_readme_real_github() {
     // Setup
     let config = Config::from_env().unwrap();
+    let target = GithubTarget::from_config(&config.github_targets[0]).unwrap();
     let github_config = Arc::new(GithubConfig {
-        token: config.github_token.clone(),
+        auth: GithubAuth::Token(config.github_token.clone().unwrap()),
         api_url: "https://api.github.com/repos".to_string(),
-        owner_repo: config.github_owner_repo.clone(),
-        readme_path: config.github_readme_path.clone(),
-        branch: config.github_branch.clone(),
+        targets: vec![target.clone()],
+        commit: CommitSettings::default(),
+        method: PublishMethod::GitData,
     });
     let service = GithubService::new(github_config);
 
     // Get current board state and valid moves
-    let mut engine = EngineService::start(&config.engine_path).await.unwrap();
+    let mut engine = EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
+        .await
+        .unwrap();
     let fen = engine.get_position().await.unwrap();
     let valid_moves = engine.get_valid_moves().await.unwrap();
     let printer = MarkdownPrinter::new(config.base_url.clone(), config.github_owner_repo.clone());
     let board_markdown = printer.print(fen, valid_moves, "");
 
     // Act
-    let result = service.update_readme(&board_markdown).await;
+    let context = CommitContext {
+        summary: "Update README".to_string(),
+        ..Default::default()
+    };
+    let result = service.update_readme(&target, &board_markdown, &context).await;
 
     // Assert
     assert!(
@@ -35,3 +46,132 @@ async fn test_update_readme_real_github() {
         result.err()
     );
 }
+
+// Helper to describe a move for the commit message
+fn move_context(player: Option<&str>, engine_san: Option<&str>) -> CommitContext {
+    CommitContext {
+        summary: "Play a move".to_string(),
+        player: player.map(str::to_string),
+        san: Some("e4".to_string()),
+        engine_san: engine_san.map(str::to_string),
+        fen: "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string(),
+        pgn: "[Result \"*\"]\n\n1. e4 e5 *\n".to_string(),
+    }
+}
+
+/// Test: Commit messages fill in the template, dropping optional sections without a value.
+#[test]
+fn test_commit_message_template() {
+    // Arrange
+    let settings = CommitSettings {
+        message_template: "♟ {player} played {san}[ — engine replied {engine_san}] [skip ci]"
+            .to_string(),
+        ..Default::default()
+    };
+    let select = CommitContext {
+        summary: "Select e2".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    let full = settings.message(&move_context(Some("octocat"), Some("e5")));
+    let anonymous = settings.message(&move_context(None, None));
+    let summary = settings.message(&select);
+
+    // Assert
+    assert_eq!(full, "♟ octocat played e4 — engine replied e5 [skip ci]");
+    assert_eq!(anonymous, "♟ A visitor played e4 [skip ci]");
+    assert_eq!(summary, "Select e2");
+}
+
+/// Test: The FEN and PGN can be appended to the commit body.
+#[test]
+fn test_commit_message_body() {
+    // Arrange
+    let settings = CommitSettings {
+        include_fen: true,
+        include_pgn: true,
+        ..Default::default()
+    };
+
+    // Act
+    let message = settings.message(&move_context(Some("octocat"), Some("e5")));
+
+    // Assert
+    assert_eq!(
+        message,
+        "octocat played e4, engine replied e5\n\n\
+         FEN: rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2\n\n\
+         [Result \"*\"]\n\n1. e4 e5 *"
+    );
+}
+
+/// Test: Targets are read from JSON, defaulting the README path and branch.
+#[test]
+fn test_targets_from_json() {
+    // Arrange
+    let json = r#"[
+        {"repo": "octocat/octocat"},
+        {"repo": "octo-org/.github", "path": "profile/README.md", "branch": "live",
+         "template": "{player} moved {san}"}
+    ]"#;
+
+    // Act
+    let configs: Vec<GithubTargetConfig> = serde_json::from_str(json).unwrap();
+    let targets: Vec<GithubTarget> = configs
+        .iter()
+        .map(|c| GithubTarget::from_config(c).unwrap())
+        .collect();
+
+    // Assert
+    assert_eq!(targets[0].label(), "octocat/octocat:README.md@main");
+    assert_eq!(targets[0].template, None);
+    assert_eq!(targets[0].readme("board"), "board");
+    assert_eq!(targets[1].label(), "octo-org/.github:profile/README.md@live");
+    assert_eq!(targets[1].template.as_deref(), Some("{player} moved {san}"));
+}
+
+// Helper to configure a target whose README template file has the given content
+fn target_with_readme_template(name: &str, template: &str) -> GithubTargetConfig {
+    let path = std::env::temp_dir().join(format!("readme-chess-template-{}.md", name));
+    std::fs::write(&path, template).unwrap();
+    GithubTargetConfig {
+        repo: "octocat/project".to_string(),
+        readme_path: "README.md".to_string(),
+        branch: "main".to_string(),
+        template: None,
+        readme_template: Some(path.to_string_lossy().to_string()),
+    }
+}
+
+/// Test: A README template file is loaded and the board is rendered into its placeholder.
+#[test]
+fn test_target_readme_template() {
+    // Arrange
+    let config = target_with_readme_template("valid", "# Project\n\n{board}\n\nDocs below.\n");
+
+    // Act
+    let target = GithubTarget::from_config(&config).unwrap();
+
+    // Assert
+    assert_eq!(target.readme("♔ board"), "# Project\n\n♔ board\n\nDocs below.\n");
+}
+
+/// Test: README templates without a board placeholder, or missing, are rejected.
+#[test]
+fn test_target_readme_template_errors() {
+    // Arrange
+    let no_placeholder = target_with_readme_template("no-placeholder", "# Project\n");
+    let missing = GithubTargetConfig {
+        readme_template: Some("/nonexistent/readme-template.md".to_string()),
+        ..no_placeholder.clone()
+    };
+
+    // Act
+    let no_placeholder_err = GithubTarget::from_config(&no_placeholder).unwrap_err();
+    let missing_err = GithubTarget::from_config(&missing).unwrap_err();
+
+    // Assert
+    assert!(no_placeholder_err.to_string().contains("{board}"), "{}", no_placeholder_err);
+    assert!(missing_err.to_string().contains("Failed to read"), "{}", missing_err);
+}
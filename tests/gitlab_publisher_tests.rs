@@ -0,0 +1,242 @@
+mod common;
+
+use actix_web::{HttpRequest, HttpResponse, web};
+use rust_readme_chess::services::gitlab_publisher::{GitlabConfig, GitlabPublisher};
+use rust_readme_chess::services::publisher::{
+    CommitContext, CommitIdentity, CommitSettings, ReadmePublisher,
+};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+/// Repository state of the fake GitLab project.
+#[derive(Default)]
+struct FakeProject {
+    readme: Option<String>,
+    /// Number of upcoming commits that lose a race against another writer
+    conflicts: u32,
+    /// Bodies of received commit requests
+    commits: Vec<Value>,
+}
+
+impl FakeProject {
+    /// Id of the last commit, which GitLab reports for the README.
+    fn last_commit_id(&self) -> String {
+        format!("commit{}", self.commits.len())
+    }
+}
+
+// Helper to start a local stand-in for the GitLab API of project "group/profile", returning
+// its base URL and the shared project state. Requests need the token "glpat-123".
+fn start_fake_gitlab(project: FakeProject) -> (String, Arc<Mutex<FakeProject>>) {
+    let project = Arc::new(Mutex::new(project));
+    let state = project.clone();
+    let base = common::start_fake_server(move |cfg| {
+        cfg.app_data(web::Data::new(state.clone()))
+            .route(
+                "/api/v4/projects/group%2Fprofile/repository/files/docs%2FREADME.md",
+                web::head().to(
+                    |req: HttpRequest, project: web::Data<Arc<Mutex<FakeProject>>>| async move {
+                        let on_main = req.query_string() == "ref=main";
+                        let project = project.lock().unwrap();
+                        match &project.readme {
+                            Some(_) if on_main => {
+                                let id = project.last_commit_id();
+                                HttpResponse::Ok()
+                                    .insert_header(("X-Gitlab-Last-Commit-Id", id))
+                                    .finish()
+                            }
+                            _ => HttpResponse::NotFound().finish(),
+                        }
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/group%2Fprofile/repository/commits",
+                web::post().to(
+                    |req: HttpRequest,
+                     project: web::Data<Arc<Mutex<FakeProject>>>,
+                     body: web::Json<Value>| async move {
+                        let token = req.headers().get("PRIVATE-TOKEN");
+                        if token.and_then(|t| t.to_str().ok()) != Some("glpat-123") {
+                            return HttpResponse::Unauthorized()
+                                .json(json!({ "message": "401 Unauthorized" }));
+                        }
+                        let mut project = project.lock().unwrap();
+                        if project.conflicts > 0 {
+                            // Someone else committed between our read and write
+                            project.conflicts -= 1;
+                            project.commits.push(json!({}));
+                            project.readme.get_or_insert_with(|| "# Other".to_string());
+                        }
+                        if body["branch"] != "main" {
+                            return HttpResponse::BadRequest().json(json!({
+                                "message": "You can only create or edit files when you are \
+                                            on a branch"
+                            }));
+                        }
+                        let action = &body["actions"][0];
+                        if action["action"] == "update"
+                            && action["last_commit_id"] != json!(project.last_commit_id())
+                        {
+                            return HttpResponse::BadRequest().json(json!({
+                                "message": "You are attempting to update a file that has \
+                                            changed since you started editing it."
+                            }));
+                        }
+                        let exists = project.readme.is_some();
+                        if (action["action"] == "create") == exists {
+                            return HttpResponse::BadRequest()
+                                .json(json!({ "message": "A file with this name already exists" }));
+                        }
+                        project.readme = action["content"].as_str().map(str::to_string);
+                        project.commits.push(body.into_inner());
+                        HttpResponse::Created().json(json!({ "id": "abc123" }))
+                    },
+                ),
+            );
+    });
+    (base, project)
+}
+
+// Helper to create a publisher for the fake project
+fn setup_publisher(base: &str, token: &str) -> GitlabPublisher {
+    setup_publisher_on_branch(base, token, "main")
+}
+
+// Helper to create a publisher committing to the given branch of the fake project
+fn setup_publisher_on_branch(base: &str, token: &str, branch: &str) -> GitlabPublisher {
+    GitlabPublisher::new(GitlabConfig {
+        token: token.to_string(),
+        api_url: format!("{}/api/v4", base),
+        project: "group/profile".to_string(),
+        readme_path: "docs/README.md".to_string(),
+        branch: branch.to_string(),
+        commit: CommitSettings {
+            author: Some(CommitIdentity {
+                name: "Chess Bot".to_string(),
+                email: "bot@example.com".to_string(),
+            }),
+            ..Default::default()
+        },
+    })
+}
+
+/// Test: The README is created on the first update and updated afterwards.
+#[actix_web::test]
+async fn test_gitlab_publish_creates_then_updates() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject::default());
+    let publisher = setup_publisher(&base, "glpat-123");
+    let context = CommitContext {
+        summary: "Start a new game".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board 1", &context).await.unwrap();
+    publisher.publish("# Board 2", &context).await.unwrap();
+
+    // Assert
+    let project = project.lock().unwrap();
+    assert_eq!(project.readme.as_deref(), Some("# Board 2"));
+    assert_eq!(project.commits[0]["actions"][0]["action"], "create");
+    assert_eq!(project.commits[1]["actions"][0]["action"], "update");
+    assert_eq!(project.commits[1]["actions"][0]["file_path"], "docs/README.md");
+    assert_eq!(project.commits[1]["commit_message"], "Start a new game");
+    assert_eq!(project.commits[1]["author_email"], "bot@example.com");
+}
+
+/// Test: An update racing another commit is retried against the new last commit.
+#[actix_web::test]
+async fn test_gitlab_publish_retries_on_conflict() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject {
+        readme: Some("# Old".to_string()),
+        conflicts: 1,
+        ..Default::default()
+    });
+    let publisher = setup_publisher(&base, "glpat-123");
+    let context = CommitContext::default();
+
+    // Act
+    publisher.publish("# Board", &context).await.unwrap();
+
+    // Assert
+    let project = project.lock().unwrap();
+    assert_eq!(project.readme.as_deref(), Some("# Board"));
+    assert_eq!(project.commits[1]["actions"][0]["last_commit_id"], "commit1");
+}
+
+/// Test: Creating the README after someone else added it is retried as an update.
+#[actix_web::test]
+async fn test_gitlab_publish_retries_racing_create() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject {
+        conflicts: 1,
+        ..Default::default()
+    });
+    let publisher = setup_publisher(&base, "glpat-123");
+    let context = CommitContext::default();
+
+    // Act
+    publisher.publish("# Board", &context).await.unwrap();
+
+    // Assert
+    let project = project.lock().unwrap();
+    assert_eq!(project.readme.as_deref(), Some("# Board"));
+    assert_eq!(project.commits[1]["actions"][0]["action"], "update");
+    assert_eq!(project.commits[1]["actions"][0]["last_commit_id"], "commit1");
+}
+
+/// Test: A rejected commit is not retried when the README did not change meanwhile.
+#[actix_web::test]
+async fn test_gitlab_publish_reports_rejected_commit() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject::default());
+    let publisher = setup_publisher_on_branch(&base, "glpat-123", "release");
+    let context = CommitContext::default();
+
+    // Act
+    let err = publisher.publish("# Board", &context).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("400"), "{}", err);
+    assert!(err.to_string().contains("on a branch"), "{}", err);
+    assert!(project.lock().unwrap().readme.is_none());
+}
+
+/// Test: An update that keeps losing races gives up instead of overwriting the README.
+#[actix_web::test]
+async fn test_gitlab_publish_gives_up_after_conflicts() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject {
+        readme: Some("# Old".to_string()),
+        conflicts: 5,
+        ..Default::default()
+    });
+    let publisher = setup_publisher(&base, "glpat-123");
+    let context = CommitContext::default();
+
+    // Act
+    let err = publisher.publish("# Board", &context).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("gave up"), "{}", err);
+    assert_eq!(project.lock().unwrap().readme.as_deref(), Some("# Old"));
+}
+
+/// Test: API errors are reported instead of silently ignored.
+#[actix_web::test]
+async fn test_gitlab_publish_reports_errors() {
+    // Arrange
+    let (base, project) = start_fake_gitlab(FakeProject::default());
+    let publisher = setup_publisher(&base, "wrong-token");
+    let context = CommitContext::default();
+
+    // Act
+    let err = publisher.publish("# Board", &context).await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("401"), "{}", err);
+    assert!(project.lock().unwrap().readme.is_none());
+}
@@ -0,0 +1,126 @@
+use rust_readme_chess::services::local_git_publisher::{LocalGitConfig, LocalGitPublisher};
+use rust_readme_chess::services::publisher::{
+    CommitContext, CommitIdentity, CommitSettings, ReadmePublisher,
+};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Helper to run git in a directory and return its trimmed output
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+// Helper to create an empty working tree and a bare remote in a fresh temp directory
+fn setup_repos(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("readme-chess-git-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let (work, remote) = (dir.join("work"), dir.join("remote.git"));
+    git(&dir, &["init", "--quiet", "--bare", "remote.git"]);
+    git(&dir, &["init", "--quiet", "work"]);
+    (work, remote)
+}
+
+// Helper to create a publisher committing as a fixed bot identity
+fn setup_publisher(work: &Path, readme_path: &str, remote: Option<&Path>) -> LocalGitPublisher {
+    let bot = CommitIdentity {
+        name: "Chess Bot".to_string(),
+        email: "bot@example.com".to_string(),
+    };
+    LocalGitPublisher::new(LocalGitConfig {
+        repo_path: work.to_path_buf(),
+        readme_path: readme_path.to_string(),
+        remote: remote.map(|r| r.display().to_string()),
+        branch: "main".to_string(),
+        commit: CommitSettings {
+            author: Some(bot.clone()),
+            committer: Some(bot),
+            ..Default::default()
+        },
+    })
+}
+
+/// Test: Each update is committed and pushed to the bare remote with its commit message.
+#[tokio::test]
+async fn test_local_git_commits_and_pushes() {
+    // Arrange
+    let (work, remote) = setup_repos("push");
+    let publisher = setup_publisher(&work, "README.md", Some(&remote));
+    let new_game = CommitContext {
+        summary: "Start a new game".to_string(),
+        ..Default::default()
+    };
+    let first_move = CommitContext {
+        player: Some("octocat".to_string()),
+        san: Some("e4".to_string()),
+        engine_san: Some("c5".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board 1\n", &new_game).await.unwrap();
+    publisher.publish("# Board 2\n", &first_move).await.unwrap();
+
+    // Assert
+    assert_eq!(git(&remote, &["show", "main:README.md"]), "# Board 2");
+    assert_eq!(
+        git(&remote, &["log", "--format=%s|%an|%ce", "main"]),
+        "octocat played e4, engine replied c5|Chess Bot|bot@example.com\n\
+         Start a new game|Chess Bot|bot@example.com"
+    );
+}
+
+/// Test: An unchanged board does not create an empty commit.
+#[tokio::test]
+async fn test_local_git_skips_unchanged_board() {
+    // Arrange
+    let (work, _) = setup_repos("unchanged");
+    let publisher = setup_publisher(&work, "README.md", None);
+    let select = CommitContext {
+        summary: "Select e2".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board\n", &select).await.unwrap();
+    publisher.publish("# Board\n", &select).await.unwrap();
+
+    // Assert
+    assert_eq!(git(&work, &["rev-list", "--count", "HEAD"]), "1");
+}
+
+/// Test: READMEs in subdirectories are created along with their directories, leaving
+/// other changes in the working tree out of the commit.
+#[tokio::test]
+async fn test_local_git_nested_readme() {
+    // Arrange
+    let (work, _) = setup_repos("nested");
+    std::fs::write(work.join("notes.txt"), "not part of the board").unwrap();
+    let publisher = setup_publisher(&work, ".github/profile/README.md", None);
+    let context = CommitContext {
+        summary: "Resign".to_string(),
+        ..Default::default()
+    };
+
+    // Act
+    publisher.publish("# Board\n", &context).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        git(&work, &["show", "--name-only", "--format=", "HEAD"]),
+        ".github/profile/README.md"
+    );
+    assert_eq!(git(&work, &["status", "--porcelain"]), "?? notes.txt");
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use actix_web::cookie::Key;
+use actix_web::http::StatusCode;
+use actix_web::test::{TestRequest, call_service, init_service};
+use actix_web::{App, HttpResponse, web};
+use rust_readme_chess::config::Config;
+use rust_readme_chess::controllers::{self, session};
+use rust_readme_chess::services::oauth_service::{OAuthConfig, OAuthService};
+use serde_json::json;
+use std::sync::Arc;
+
+// Helper to start a local stand-in for GitHub's OAuth endpoints, returning its base URL.
+// It accepts the code "good-code", issues the token "token-123" and knows the user "octocat",
+// a member of the "chess-club" organization.
+fn start_fake_provider() -> String {
+    common::start_fake_server(|cfg| {
+        cfg.route(
+            "/login/oauth/access_token",
+            web::post().to(|form: web::Form<Vec<(String, String)>>| async move {
+                let code = form.iter().find(|(k, _)| k == "code").map(|(_, v)| v.as_str());
+                if code == Some("good-code") {
+                    HttpResponse::Ok().json(json!({ "access_token": "token-123" }))
+                } else {
+                    HttpResponse::Ok().json(json!({
+                        "error": "bad_verification_code",
+                        "error_description": "The code passed is incorrect or expired."
+                    }))
+                }
+            }),
+        )
+        .route(
+            "/user",
+            web::get().to(|req: actix_web::HttpRequest| async move {
+                let auth = req.headers().get("Authorization").and_then(|h| h.to_str().ok());
+                if auth == Some("Bearer token-123") {
+                    HttpResponse::Ok().json(json!({ "login": "octocat" }))
+                } else {
+                    HttpResponse::Unauthorized().finish()
+                }
+            }),
+        )
+        .route(
+            "/user/memberships/orgs/{org}",
+            web::get().to(|org: web::Path<String>| async move {
+                if org.as_str() == "chess-club" {
+                    HttpResponse::Ok().json(json!({ "state": "active" }))
+                } else {
+                    HttpResponse::NotFound().finish()
+                }
+            }),
+        );
+    })
+}
+
+// Helper to create an OAuthService pointed at the fake provider
+fn setup_oauth(base: &str) -> OAuthService {
+    OAuthService::new(Arc::new(OAuthConfig {
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        authorize_url: format!("{}/login/oauth/authorize", base),
+        token_url: format!("{}/login/oauth/access_token", base),
+        user_url: format!("{}/user", base),
+        org_membership_url: format!("{}/user/memberships/orgs/{{org}}", base),
+        scope: Some("read:org".to_string()),
+        redirect_url: "http://localhost/oauth/callback".to_string(),
+    }))
+}
+
+/// Test: The authorize URL carries the client id, callback, scope and state.
+#[test]
+fn test_oauth_authorize_url() {
+    // Arrange
+    let oauth = setup_oauth("https://github.com");
+
+    // Act
+    let url = oauth.authorize_url("xyz");
+
+    // Assert
+    assert_eq!(
+        url,
+        "https://github.com/login/oauth/authorize?client_id=client&redirect_uri=http%3A%2F%2Flocalhost%2Foauth%2Fcallback&scope=read%3Aorg&state=xyz"
+    );
+}
+
+/// Test: A valid code is exchanged for a token, which identifies the GitHub user.
+#[actix_web::test]
+async fn test_oauth_exchange_and_fetch_login() {
+    // Arrange
+    let oauth = setup_oauth(&start_fake_provider());
+
+    // Act
+    let token = oauth.exchange_code("good-code").await.unwrap();
+    let login = oauth.fetch_login(&token).await.unwrap();
+
+    // Assert
+    assert_eq!(token, "token-123");
+    assert_eq!(login, "octocat");
+}
+
+/// Test: A rejected code reports the provider's error.
+#[actix_web::test]
+async fn test_oauth_rejected_code() {
+    // Arrange
+    let oauth = setup_oauth(&start_fake_provider());
+
+    // Act
+    let err = oauth.exchange_code("expired").await.unwrap_err();
+
+    // Assert
+    assert!(err.to_string().contains("incorrect or expired"), "got: {}", err);
+}
+
+/// Test: Organization membership is reported for members and denied for other organizations.
+#[actix_web::test]
+async fn test_oauth_org_membership() {
+    // Arrange
+    let oauth = setup_oauth(&start_fake_provider());
+
+    // Act
+    let member = oauth.is_org_member("token-123", "chess-club").await.unwrap();
+    let outsider = oauth.is_org_member("token-123", "other-org").await.unwrap();
+
+    // Assert
+    assert!(member);
+    assert!(!outsider);
+}
+
+// Helper to send an OAuth callback for "octocat" through the app's routes, with the given
+// allowed organization and pending return path.
+async fn sign_in(allowed_org: Option<&str>, return_to: Option<&str>) -> HttpResponse {
+    let mut config = Config::from_env().unwrap();
+    config.base_url = "http://localhost".to_string();
+    config.allowed_org = allowed_org.map(str::to_string);
+    let key = Key::generate();
+    let oauth = Arc::new(setup_oauth(&start_fake_provider()));
+    let app = init_service(
+        App::new()
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(key.clone()))
+            .app_data(web::Data::new(oauth))
+            .configure(controllers::init_routes),
+    )
+    .await;
+    let mut req = TestRequest::get()
+        .uri("/oauth/callback?code=good-code&state=xyz")
+        .cookie(session::signed_cookie(&key, session::STATE_COOKIE, "xyz"));
+    if let Some(path) = return_to {
+        req = req.cookie(session::signed_cookie(&key, session::RETURN_COOKIE, path));
+    }
+    call_service(&app, req.to_request()).await.into_parts().1
+}
+
+/// Test: Signing in resumes the request that required login and sets the session cookie.
+#[actix_web::test]
+async fn test_oauth_callback_returns_to_request() {
+    // Act
+    let resp = sign_in(Some("chess-club"), Some("/play?mv=e2e4")).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        resp.headers().get("Location").unwrap(),
+        "http://localhost/play?mv=e2e4"
+    );
+    assert!(resp.cookies().any(|c| c.name() == session::SESSION_COOKIE));
+}
+
+/// Test: Visitors outside the allowed organization are refused a session.
+#[actix_web::test]
+async fn test_oauth_callback_denies_other_org() {
+    // Act
+    let resp = sign_in(Some("other-org"), Some("/play?mv=e2e4")).await;
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert!(!resp.cookies().any(|c| c.name() == session::SESSION_COOKIE));
+}
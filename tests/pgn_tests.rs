@@ -0,0 +1,46 @@
+use rust_readme_chess::utils::pgn::{san_moves, write_pgn};
+
+/// Test: A standard game is written with tags, SAN moves and the result.
+#[test]
+fn test_pgn_standard_game() {
+    // Arrange
+    let moves: Vec<String> = ["e2e4", "e7e5", "d1h5", "b8c6", "f1c4", "g8f6", "h5f7"]
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+    let tags = [("Event", "Test".to_string()), ("White", "Visitors".to_string())];
+
+    // Act
+    let pgn = write_pgn(&tags, None, false, &moves, "1-0");
+
+    // Assert
+    assert_eq!(
+        pgn,
+        "[Event \"Test\"]\n[White \"Visitors\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0\n"
+    );
+}
+
+/// Test: Games from a custom position record the FEN and number moves from it.
+#[test]
+fn test_pgn_custom_start() {
+    // Arrange
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12";
+    let moves = vec!["e8d7".to_string(), "e2e4".to_string()];
+
+    // Act
+    let pgn = write_pgn(&[], Some(fen), false, &moves, "*");
+
+    // Assert
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n"));
+    assert!(pgn.ends_with("12... Kd7 13. e4 *\n"));
+}
+
+/// Test: UCI moves are converted to SAN, stopping at an unplayable move.
+#[test]
+fn test_pgn_san_moves() {
+    let moves: Vec<String> = ["e2e4", "e7e5", "g1f3", "e1e3", "b8c6"]
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+    assert_eq!(san_moves(None, false, &moves), vec!["e4", "e5", "Nf3"]);
+}
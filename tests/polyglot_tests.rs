@@ -0,0 +1,48 @@
+use rust_readme_chess::utils::polyglot::PolyglotBook;
+
+// Polyglot key of the standard starting position
+const INITIAL_KEY: u64 = 0x463b_9618_1691_fc9c;
+const INITIAL_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+// Helper to encode a book entry (squares are 0..64 with a1 = 0)
+fn entry(key: u64, from: u16, to: u16, weight: u16) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(&key.to_be_bytes());
+    bytes.extend_from_slice(&((from << 6) | to).to_be_bytes());
+    bytes.extend_from_slice(&weight.to_be_bytes());
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes
+}
+
+/// Test: Book moves for the starting position are found, legal-only and ordered by weight.
+#[test]
+fn test_book_moves_for_initial_position() {
+    // Arrange
+    let mut bytes = entry(INITIAL_KEY, 11, 27, 10); // d2d4
+    bytes.extend(entry(INITIAL_KEY, 12, 28, 30)); // e2e4
+    bytes.extend(entry(INITIAL_KEY, 12, 36, 50)); // e2e5 (illegal)
+    let book = PolyglotBook::from_bytes(&bytes).unwrap();
+
+    // Act
+    let moves = book.moves(INITIAL_POSITION);
+
+    // Assert
+    let ucis: Vec<&str> = moves.iter().map(|m| m.uci.as_str()).collect();
+    assert_eq!(ucis, vec!["e2e4", "d2d4"]);
+    let picked = book.pick(INITIAL_POSITION).unwrap();
+    assert!(ucis.contains(&picked.as_str()));
+}
+
+/// Test: Positions outside the book yield no moves, and truncated books are rejected.
+#[test]
+fn test_book_misses_and_invalid_data() {
+    // Arrange
+    let book = PolyglotBook::from_bytes(&entry(INITIAL_KEY, 12, 28, 1)).unwrap();
+
+    // Act
+    let after_e4 = book.pick("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+
+    // Assert
+    assert_eq!(after_e4, None);
+    assert!(PolyglotBook::from_bytes(&[0u8; 15]).is_err());
+}
@@ -0,0 +1,65 @@
+use rust_readme_chess::utils::positions::{find, repetition_key, validate_fen, CATALOG};
+
+/// Test: Every named position in the catalog is a legal, playable FEN.
+#[test]
+fn test_catalog_positions_are_valid() {
+    for position in CATALOG {
+        assert!(
+            validate_fen(position.fen).is_ok(),
+            "{} should be valid",
+            position.key
+        );
+    }
+}
+
+/// Test: Named positions are found by key and unknown keys are rejected.
+#[test]
+fn test_find_looks_up_by_key() {
+    // Act
+    let lucena = find("lucena");
+    let missing = find("no-such-position");
+
+    // Assert
+    assert_eq!(lucena.map(|p| p.name), Some("Lucena position"));
+    assert!(missing.is_none());
+}
+
+/// Test: Malformed and illegal FENs produce descriptive errors.
+#[test]
+fn test_validate_fen_rejects_bad_input() {
+    // Act
+    let malformed = validate_fen("not a fen");
+    let two_kings = validate_fen("8/8/8/4k3/8/8/4K3/4K3 w - - 0 1");
+    let stalemate = validate_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+
+    // Assert
+    assert!(malformed.unwrap_err().starts_with("Invalid FEN"));
+    assert!(two_kings.unwrap_err().starts_with("Illegal position"));
+    assert!(stalemate.unwrap_err().contains("no legal moves"));
+}
+
+/// Test: A valid FEN is returned in normalized form.
+#[test]
+fn test_validate_fen_normalizes_whitespace() {
+    // Act
+    let fen = validate_fen("  8/8/8/4k3/8/8/8/R3K3 w - - 0 1 ");
+
+    // Assert
+    assert_eq!(fen.unwrap(), "8/8/8/4k3/8/8/8/R3K3 w - - 0 1");
+}
+
+/// Test: Chess960 castling rights compare equal in standard and Shredder notation.
+#[test]
+fn test_repetition_key_normalizes_castling() {
+    // Arrange
+    let start = "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1";
+    let from_engine = "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 4 3";
+    let lost_castling = "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w Hh - 4 3";
+
+    // Act
+    let key = repetition_key(start);
+
+    // Assert
+    assert_eq!(key, repetition_key(from_engine));
+    assert_ne!(key, repetition_key(lost_castling));
+}
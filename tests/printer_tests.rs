@@ -1,11 +1,17 @@
 use rust_readme_chess::config::Config;
-use rust_readme_chess::services::engine_service::EngineService;
+use rust_readme_chess::services::archive_service::{ArchivedGame, LeaderboardEntry, MoveRecord};
+use rust_readme_chess::services::chess_service::{
+    CapturedPieces, ClockState, GameResult, Outcome, PuzzleOutcome, PuzzleProgress,
+};
+use rust_readme_chess::services::engine_service::{EngineService, Score, SearchInfo};
+use rust_readme_chess::utils::eco;
 use rust_readme_chess::utils::printer::MarkdownPrinter;
+use std::time::Duration;
 
 // Helper to start Stockfish for tests using the same config pattern as the main app
 async fn setup_engine() -> EngineService {
     let config = Config::from_env().unwrap();
-    EngineService::start(&config.engine_path)
+    EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
         .await
         .expect("Failed to start engine")
 }
@@ -48,7 +54,9 @@ Welcome to my GitHub profile! Here, you can play a game of chess with me, using
 |  **2**  |  [**P**]({0}/select?square=a2)  |  [**P**]({0}/select?square=b2)  |  [**P**]({0}/select?square=c2)  |  [**P**]({0}/select?square=d2)  |  [**P**]({0}/select?square=e2)  |  [**P**]({0}/select?square=f2)  |  [**P**]({0}/select?square=g2)  |  [**P**]({0}/select?square=h2)  |
 |  **1**  |  [**R**](https://github.com/{1})  |  [**N**]({0}/select?square=b1)  |  [**B**](https://github.com/{1})  |  [**Q**](https://github.com/{1})  |  [**K**](https://github.com/{1})  |  [**B**](https://github.com/{1})  |  [**N**]({0}/select?square=g1)  |  [**R**](https://github.com/{1})  |
 
-[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new)"#,
+**Status:** White to move
+
+[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new) [![Hint](https://img.shields.io/badge/Hint-2196F3)]({0}/hint) [![Resign](https://img.shields.io/badge/Resign-F44336)]({0}/resign) [![Draw](https://img.shields.io/badge/Draw-9E9E9E)]({0}/draw)"#,
         base_url,
         config.github_owner_repo
     );
@@ -98,7 +106,9 @@ Welcome to my GitHub profile! Here, you can play a game of chess with me, using
 |  **2**  |  [**P**]({0}/select?square=a2)  |  [**P**]({0}/select?square=b2)  |  [**P**]({0}/select?square=c2)  |  [**P**]({0}/select?square=d2)  |  [**P**]({0}/select?square=e2)  |  [**P**]({0}/select?square=f2)  |  [**P**]({0}/select?square=g2)  |  [**P**]({0}/select?square=h2)  |
 |  **1**  |  [**R**](https://github.com/{1})  |  [**N**]({0}/select?square=b1)  |  [**B**](https://github.com/{1})  |  [**Q**](https://github.com/{1})  |  [**K**](https://github.com/{1})  |  [**B**](https://github.com/{1})  |  [**N**]({0}/select?square=g1)  |  [**R**](https://github.com/{1})  |
 
-[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new)"#,
+**Status:** White to move
+
+[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new) [![Hint](https://img.shields.io/badge/Hint-2196F3)]({0}/hint) [![Resign](https://img.shields.io/badge/Resign-F44336)]({0}/resign) [![Draw](https://img.shields.io/badge/Draw-9E9E9E)]({0}/draw)"#,
         base_url,
         config.github_owner_repo
     );
@@ -150,7 +160,9 @@ Welcome to my GitHub profile! Here, you can play a game of chess with me, using
 |  **2**  |  [**P**]({0}/select?square=a2)  |  [**P**]({0}/select?square=b2)  |  [**P**]({0}/select?square=c2)  |  [**P**]({0}/select?square=d2)  |     |  [**P**]({0}/select?square=f2)  |  [**P**]({0}/select?square=g2)  |  [**P**]({0}/select?square=h2)  |
 |  **1**  |  [**R**](https://github.com/{1})  |  [**N**]({0}/select?square=b1)  |  [**B**](https://github.com/{1})  |  [**Q**]({0}/select?square=d1)  |  [**K**]({0}/select?square=e1)  |  [**B**]({0}/select?square=f1)  |  [**N**]({0}/select?square=g1)  |  [**R**](https://github.com/{1})  |
 
-[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new)"#,
+**Status:** White to move
+
+[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new) [![Hint](https://img.shields.io/badge/Hint-2196F3)]({0}/hint) [![Resign](https://img.shields.io/badge/Resign-F44336)]({0}/resign) [![Draw](https://img.shields.io/badge/Draw-9E9E9E)]({0}/draw)"#,
         base_url,
         config.github_owner_repo
     );
@@ -202,7 +214,9 @@ Welcome to my GitHub profile! Here, you can play a game of chess with me, using
 |  **2**  |  [**P**]({0}/select?square=a2)  |  [**P**]({0}/select?square=b2)  |  [**P**]({0}/select?square=c2)  |  [**P**]({0}/select?square=d2)  |  [_]({0}/play?mv=d1e2)  |  [**P**]({0}/select?square=f2)  |  [**P**]({0}/select?square=g2)  |  [**P**]({0}/select?square=h2)  |
 |  **1**  |  [**R**](https://github.com/{1})  |  [**N**]({0}/select?square=b1)  |  [**B**](https://github.com/{1})  |  [**Q**](https://github.com/{1})  |  [**K**](https://github.com/{1})  |  [**B**](https://github.com/{1})  |  [**N**]({0}/select?square=g1)  |  [**R**](https://github.com/{1})  |
 
-[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new)"#,
+**Status:** White to move
+
+[![New Game](https://img.shields.io/badge/New_Game-4CAF50)]({0}/new) [![Hint](https://img.shields.io/badge/Hint-2196F3)]({0}/hint) [![Resign](https://img.shields.io/badge/Resign-F44336)]({0}/resign) [![Draw](https://img.shields.io/badge/Draw-9E9E9E)]({0}/draw)"#,
         base_url,
         config.github_owner_repo
     );
@@ -213,3 +227,324 @@ Welcome to my GitHub profile! Here, you can play a game of chess with me, using
         "Markdown output does not match the expected output after move e2e4 and engine reply c7c5"
     );
 }
+
+/// Test: A finished game shows its result and no longer offers resign/draw links.
+#[test]
+fn test_printer_shows_result() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_result(Some(GameResult::new(Outcome::BlackWins, "White resigns")));
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Result:** 0-1 (White resigns)"));
+    assert!(md.contains("(http://localhost/new)"));
+    assert!(!md.contains("/resign"), "Resign link should be hidden after the game ends");
+    assert!(!md.contains("select?square="), "No piece should be selectable after the game ends");
+}
+
+/// Test: The player's pieces link to the configured profile page instead of GitHub.
+#[test]
+fn test_printer_links_pieces_to_profile_url() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_profile_url("https://gitlab.com/group/profile".to_string());
+
+    // Act
+    let md = printer.print("7k/8/8/8/8/8/8/R3K3 w - - 0 1".to_string(), Vec::new(), "");
+
+    // Assert
+    assert!(md.contains("(https://gitlab.com/group/profile)"), "{}", md);
+    assert!(!md.contains("(https://github.com/owner)"), "{}", md);
+}
+
+/// Test: Selecting a pawn on the seventh rank offers a link per promotion piece.
+#[test]
+fn test_printer_promotion_choices() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string());
+    let valid_moves = ["e7e8q", "e7e8r", "e7e8b", "e7e8n", "e1d1"]
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+
+    // Act
+    let md = printer.print("7k/4P3/8/8/8/8/8/4K3 w - - 0 1".to_string(), valid_moves, "e7");
+
+    // Assert
+    assert!(md.contains(
+        "[Q](http://localhost/play?mv=e7e8q) [R](http://localhost/play?mv=e7e8r) \
+         [B](http://localhost/play?mv=e7e8b) [N](http://localhost/play?mv=e7e8n)"
+    ));
+    assert!(!md.contains("play?mv=e7e8)"), "Bare promotion move should not be linked");
+}
+
+/// Test: A king in check and the checking piece are marked, with a check status line.
+#[test]
+fn test_printer_marks_check() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_checkers(vec!["b4".to_string()]);
+
+    // Act
+    let md = printer.print(
+        "rnbqk1nr/pppp1ppp/8/4p3/1b1P4/8/PPP1PPPP/RNBQKBNR w KQkq - 1 3".to_string(),
+        vec!["c2c3".to_string(), "b1c3".to_string()],
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("🚨[**K**](https://github.com/owner)"));
+    assert!(md.contains("⚔️_b_"));
+    assert!(md.contains("**Status:** White to move — in check"));
+}
+
+/// Test: Captured pieces are listed per side with the material lead of the side ahead.
+#[test]
+fn test_printer_captured_row() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_captured(CapturedPieces {
+            by_white: vec!['n', 'p'],
+            by_black: vec!['P'],
+            balance: 3,
+        });
+
+    // Act
+    let md = printer.print(
+        "r1bqkbnr/pppp1ppp/8/8/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 4".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Captured:** White: _n_ _p_ (+3) · Black: **P**"));
+}
+
+/// Test: Engine analysis renders an evaluation bar and the expected continuation.
+#[test]
+fn test_printer_evaluation_and_best_line() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_analysis(Some(SearchInfo {
+            depth: 16,
+            score: Score::Cp(-230),
+            pv: vec!["g1f3".to_string(), "b8c6".to_string()],
+        }));
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Evaluation:** -2.30 ⬜⬜⬜⬛⬛⬛⬛⬛⬛⬛ (depth 16)"));
+    assert!(md.contains("**Engine expects:** g1f3 b8c6"));
+}
+
+/// Test: A book reply is marked with the name of the opening instead of an evaluation.
+#[test]
+fn test_printer_book_move_names_opening() {
+    // Arrange
+    let moves = ["e2e4".to_string(), "c7c5".to_string()];
+    let opening = eco::lookup(&moves).cloned().expect("Sicilian should be in the ECO table");
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_book(true)
+        .with_opening(Some(opening.clone()));
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    let marker = format!("**Evaluation:** 📖 book move: {}", opening);
+    assert!(md.contains(&marker), "{}", md);
+}
+
+/// Test: A hint highlights the suggested move's squares and shows the hint counter.
+#[test]
+fn test_printer_highlights_hint() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_hint(Some("e2e4".to_string()), 2);
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        vec!["e2e4".to_string(), "e2e3".to_string()],
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("💡[**P**](http://localhost/select?square=e2)"));
+    assert!(md.contains("|  💡   |"), "Empty target square should be highlighted");
+    assert!(md.contains("**Hints used:** 2"));
+}
+
+/// Test: Puzzle mode shows the puzzle, its outcome and the streak, without Resign or Draw.
+#[test]
+fn test_printer_shows_puzzle_status() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_puzzle(Some(PuzzleProgress {
+            id: "back-rank".to_string(),
+            outcome: PuzzleOutcome::Solved,
+            streak: 3,
+        }));
+
+    // Act
+    let md = printer.print(
+        "3R2k1/5ppp/8/8/8/8/5PPP/6K1 b - - 0 2".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Puzzle:** back-rank"));
+    assert!(md.contains("**Puzzle:** ✅ Solved!"));
+    assert!(md.contains("**Streak:** 3"));
+    assert!(!md.contains("/resign") && !md.contains("/draw"));
+}
+
+/// Test: In human-vs-human mode Black's pieces are selectable when it is Black's turn.
+#[test]
+fn test_printer_human_vs_human_black_to_move() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_human_vs_human(true);
+    let valid_moves = vec!["e7e5".to_string(), "g8f6".to_string()];
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1".to_string(),
+        valid_moves,
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Mode:** Human vs human"));
+    assert!(md.contains("(http://localhost/select?square=e7)"));
+    assert!(md.contains("(http://localhost/select?square=g8)"));
+    assert!(!md.contains("select?square=e4"), "White pieces should not be selectable");
+    assert!(md.contains("**Status:** Black to move"));
+}
+
+/// Test: The move clock shows the remaining time per side and marks the running clock.
+#[test]
+fn test_printer_shows_clock() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_clock(Some(ClockState {
+            white: Duration::from_secs(29 * 3_600),
+            black: Duration::from_secs(2 * 86_400),
+            per_move: Duration::from_secs(2 * 86_400),
+            white_to_move: true,
+        }));
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Clock:** ⏳ White 1d 5h · Black 2d 0h (2d 0h per move)"));
+}
+
+/// Test: Recently archived games are listed with their result and a PGN link.
+#[test]
+fn test_printer_recent_games() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_recent_games(vec![ArchivedGame {
+            id: 7,
+            started_at: 1_792_300_000,
+            ended_at: 1_792_368_000,
+            white: "Visitors".to_string(),
+            black: "Stockfish".to_string(),
+            result: "0-1".to_string(),
+            reason: "Checkmate".to_string(),
+            plies: 15,
+            start_fen: None,
+            players: Vec::new(),
+        }]);
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("### Recent games"));
+    assert!(md.contains(
+        "| 2026-10-19 | Visitors | Stockfish | 0-1 (Checkmate) | 8 | [PGN](http://localhost/archive/7.pgn) |"
+    ));
+}
+
+/// Test: Signed-in visitors are credited for recent moves and ranked on the leaderboard.
+#[test]
+fn test_printer_recent_moves_and_leaderboard() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_recent_moves(vec![MoveRecord {
+            login: "alice".to_string(),
+            uci: "e2e4".to_string(),
+            at: 1_792_368_000,
+        }])
+        .with_leaderboard(vec![LeaderboardEntry {
+            login: "alice".to_string(),
+            moves: 12,
+            wins: 2,
+        }]);
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("**Recent moves by:** [@alice](https://github.com/alice) (e2e4)"));
+    assert!(md.contains("### Leaderboard"));
+    assert!(md.contains("| 1 | [@alice](https://github.com/alice) | 12 | 2 |"));
+}
+
+/// Test: Profile links point at the configured web root, e.g. a GitHub Enterprise server.
+#[test]
+fn test_printer_enterprise_web_url() {
+    // Arrange
+    let printer = MarkdownPrinter::new("http://localhost".to_string(), "owner".to_string())
+        .with_web_url("https://github.example.com".to_string())
+        .with_recent_moves(vec![MoveRecord {
+            login: "alice".to_string(),
+            uci: "e2e4".to_string(),
+            at: 1_792_368_000,
+        }]);
+
+    // Act
+    let md = printer.print(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        Vec::new(),
+        "",
+    );
+
+    // Assert
+    assert!(md.contains("(https://github.example.com/owner)"));
+    assert!(md.contains("[@alice](https://github.example.com/alice)"));
+    assert!(!md.contains("https://github.com/owner"));
+}
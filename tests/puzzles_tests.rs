@@ -0,0 +1,58 @@
+use rust_readme_chess::utils::puzzles::PuzzleSet;
+
+const BACK_RANK: &str = "back-rank,r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1,d2d8 a8d8 d1d8";
+
+/// Test: A puzzle line is parsed into its id, position and solution.
+#[test]
+fn test_puzzles_parse_line() {
+    // Act
+    let set = PuzzleSet::parse(BACK_RANK).unwrap();
+
+    // Assert
+    let puzzle = set.daily(0);
+    assert_eq!(puzzle.id, "back-rank");
+    assert_eq!(puzzle.fen, "r5k1/5ppp/8/8/8/8/3R1PPP/3R2K1 w - - 0 1");
+    assert_eq!(puzzle.solution, vec!["d2d8", "a8d8", "d1d8"]);
+}
+
+/// Test: Comments, malformed lines, Black-to-move positions and illegal solutions are skipped.
+#[test]
+fn test_puzzles_skip_invalid_entries() {
+    // Arrange
+    let contents = format!(
+        "# id,fen,moves\n\n{}\nno-fields\nblack,6k1/8/8/8/8/8/8/R5K1 b - - 0 1,g8h8\nillegal,6k1/8/8/8/8/8/8/R5K1 w - - 0 1,a1h8\n",
+        BACK_RANK
+    );
+
+    // Act
+    let set = PuzzleSet::parse(&contents).unwrap();
+
+    // Assert
+    assert_eq!(set.daily(0).id, "back-rank");
+    assert_eq!(
+        set.daily(1).id,
+        "back-rank",
+        "Only one puzzle should be loaded"
+    );
+    assert!(PuzzleSet::parse("# nothing here\n").is_err());
+}
+
+/// Test: The daily puzzle cycles through the set by day.
+#[test]
+fn test_puzzles_daily_cycles() {
+    // Arrange
+    let contents = format!(
+        "{}\nrook-mate,6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1,d1d8",
+        BACK_RANK
+    );
+    let set = PuzzleSet::parse(&contents).unwrap();
+
+    // Act
+    let ids: Vec<&str> = (0..4).map(|day| set.daily(day).id.as_str()).collect();
+
+    // Assert
+    assert_eq!(
+        ids,
+        vec!["back-rank", "rook-mate", "back-rank", "rook-mate"]
+    );
+}
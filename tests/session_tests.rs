@@ -0,0 +1,117 @@
+use actix_web::cookie::Key;
+use actix_web::http::StatusCode;
+use actix_web::test::{TestRequest, call_service, init_service};
+use actix_web::{App, web};
+use rust_readme_chess::config::Config;
+use rust_readme_chess::controllers::{self, session};
+use rust_readme_chess::services::chess_service::ChessService;
+use rust_readme_chess::services::engine_service::EngineService;
+use rust_readme_chess::services::file_publisher::{FileConfig, FilePublisher};
+use rust_readme_chess::services::publisher::{CommitSettings, ReadmePublisher};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// Helper to load the config with login required or not
+fn setup_config(require_login: bool) -> Config {
+    let mut config = Config::from_env().unwrap();
+    config.base_url = "http://localhost".to_string();
+    config.require_login = require_login;
+    config
+}
+
+/// Test: Anonymous visitors are sent to /login and the request is kept to resume later.
+#[test]
+fn test_require_login_redirects_anonymous_visitor() {
+    // Arrange
+    let config = setup_config(true);
+    let key = Key::generate();
+    let req = TestRequest::get().uri("/play?mv=e2e4").to_http_request();
+
+    // Act
+    let resp = session::signed_in_user(&req, &config, &key).unwrap_err();
+
+    // Assert
+    assert_eq!(resp.status(), StatusCode::SEE_OTHER);
+    assert_eq!(resp.headers().get("Location").unwrap(), "http://localhost/login");
+    let cookie = resp
+        .cookies()
+        .find(|c| c.name() == session::RETURN_COOKIE)
+        .expect("Return cookie should be set");
+    let resumed = TestRequest::get().cookie(cookie.into_owned()).to_http_request();
+    assert_eq!(
+        session::read_signed(&resumed, &key, session::RETURN_COOKIE).as_deref(),
+        Some("/play?mv=e2e4")
+    );
+}
+
+/// Test: Signed-in visitors pass, and anonymous ones too when login is optional.
+#[test]
+fn test_signed_in_user() {
+    // Arrange
+    let key = Key::generate();
+    let signed_in = TestRequest::get()
+        .cookie(session::session_cookie(&key, "octocat"))
+        .to_http_request();
+    let anonymous = TestRequest::get().to_http_request();
+
+    // Act
+    let required = session::signed_in_user(&signed_in, &setup_config(true), &key);
+    let optional = session::signed_in_user(&anonymous, &setup_config(false), &key);
+
+    // Assert
+    assert_eq!(required.ok().flatten().as_deref(), Some("octocat"));
+    assert_eq!(optional.ok().flatten(), None);
+}
+
+/// Test: A forged return cookie is ignored.
+#[test]
+fn test_forged_cookie_is_ignored() {
+    // Arrange
+    let key = Key::generate();
+    let forged = session::signed_cookie(&Key::generate(), session::RETURN_COOKIE, "/new");
+    let req = TestRequest::get().cookie(forged).to_http_request();
+
+    // Act
+    let value = session::read_signed(&req, &key, session::RETURN_COOKIE);
+
+    // Assert
+    assert_eq!(value, None);
+}
+
+/// Test: Resigning and starting the puzzle redirect anonymous visitors when login is required.
+#[actix_web::test]
+async fn test_state_changing_routes_require_login() {
+    // Arrange
+    let config = setup_config(true);
+    let engine = EngineService::start(&config.engine_path, config.syzygy_path.as_deref())
+        .await
+        .expect("Failed to start engine");
+    let chess_service = Arc::new(Mutex::new(ChessService::new(engine)));
+    let dir = std::env::temp_dir().join("readme-chess-require-login");
+    let _ = std::fs::remove_dir_all(&dir);
+    let readme = dir.join("README.md");
+    let publisher: Arc<dyn ReadmePublisher> = Arc::new(FilePublisher::new(FileConfig {
+        path: readme.clone(),
+        commit: CommitSettings::default(),
+    }));
+    let app = init_service(
+        App::new()
+            .app_data(web::Data::new(config.clone()))
+            .app_data(web::Data::new(chess_service.clone()))
+            .app_data(web::Data::new(publisher))
+            .app_data(web::Data::new(Key::generate()))
+            .configure(controllers::init_routes),
+    )
+    .await;
+
+    for uri in ["/resign", "/puzzle"] {
+        // Act
+        let resp = call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+
+        // Assert
+        assert_eq!(resp.status(), StatusCode::SEE_OTHER, "{}", uri);
+        assert_eq!(resp.headers().get("Location").unwrap(), "http://localhost/login");
+    }
+    assert!(chess_service.lock().await.get_result().is_none());
+    assert!(!readme.exists());
+}
@@ -0,0 +1,73 @@
+use rust_readme_chess::utils::tablebase::{Tablebase, Wdl};
+
+// Helper to open the KQvK and KRvK tables bundled as test fixtures
+fn setup_tablebase() -> Tablebase {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy");
+    Tablebase::open(dir).unwrap()
+}
+
+/// Test: Covered positions are probed for their value and distance to zeroing.
+#[test]
+fn test_tablebase_probe_win() {
+    // Arrange
+    let tb = setup_tablebase();
+
+    // Act
+    let white = tb.probe("8/8/8/4k3/8/8/8/3QK3 w - - 0 1").unwrap();
+    let black = tb.probe("8/8/8/4k3/8/8/8/3QK3 b - - 0 1").unwrap();
+
+    // Assert
+    assert_eq!(white.wdl, Wdl::Win);
+    assert_eq!(white.white_wdl(), Wdl::Win);
+    assert_eq!(
+        white.to_string(),
+        "White to move wins; 13 plies to the next capture or pawn move"
+    );
+    assert_eq!(black.wdl, Wdl::Loss);
+    assert_eq!(black.white_wdl(), Wdl::Win);
+    assert!(black.to_string().starts_with("Black to move loses; "), "{}", black);
+}
+
+/// Test: Tablebase draws are reported without a distance.
+#[test]
+fn test_tablebase_probe_draw() {
+    // Arrange
+    let tb = setup_tablebase();
+
+    // Act: Black to move captures the undefended queen
+    let probe = tb.probe("8/8/8/8/8/8/3kQ3/6K1 b - - 0 1").unwrap();
+
+    // Assert
+    assert_eq!(probe.wdl, Wdl::Draw);
+    assert_eq!(probe.to_string(), "draw");
+}
+
+/// Test: Positions without tables are not adjudicated, even if the engine would call them
+/// level.
+#[test]
+fn test_tablebase_ignores_uncovered_positions() {
+    // Arrange
+    let tb = setup_tablebase();
+
+    // Act & Assert: KQ vs KQ is a dead draw by evaluation but has no table here
+    assert_eq!(tb.probe("8/8/8/4k3/3q4/8/8/3QK3 w - - 0 1"), None);
+    assert_eq!(
+        tb.probe("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        None
+    );
+}
+
+/// Test: Positions with castling rights are outside the tablebases.
+#[test]
+fn test_tablebase_ignores_castling_rights() {
+    // Arrange
+    let tb = setup_tablebase();
+
+    // Act
+    let castling = tb.probe("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1");
+    let no_castling = tb.probe("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+
+    // Assert
+    assert_eq!(castling, None);
+    assert_eq!(no_castling.map(|p| p.wdl), Some(Wdl::Win));
+}